@@ -1,23 +1,85 @@
+use std::fmt;
+
+use crate::span::{Span, Spanned};
+
 #[derive(Debug)]
 pub enum Expr {
     Variant {
-        variant_group: Vec<Option<Variant>>,
-        variants: Vec<Variant>,
+        variant_group: Option<VariantGroup>,
+        variants: Vec<Spanned<Variant>>,
         exprs: Vec<Expr>,
     },
     Util(Util),
-    RawCss(String),
+    RawCss(Spanned<String>),
+}
+
+impl Expr {
+    /// Moves every span of the expression to `span`, for code spliced in from
+    /// elsewhere
+    pub fn respan(&mut self, span: Span) {
+        match self {
+            Expr::Variant {
+                variant_group,
+                variants,
+                exprs,
+            } => {
+                if let Some(variant_group) = variant_group {
+                    variant_group.span = span;
+                    for variant in &mut variant_group.variants {
+                        variant.span = span;
+                    }
+                }
+                for variant in variants {
+                    variant.span = span;
+                }
+                for expr in exprs {
+                    expr.respan(span);
+                }
+            }
+            Expr::Util(util) => {
+                util.span = span;
+                for property in &mut util.properties {
+                    property.span = span;
+                }
+                match &mut util.tree {
+                    UtilTree::Leaf(Some(value)) => {
+                        value.span = span;
+                        if let ValueOrGroup::Group(values) = &mut value.node {
+                            for value in values {
+                                value.span = span;
+                            }
+                        }
+                    }
+                    UtilTree::Leaf(None) => {}
+                    UtilTree::Branch(exprs) => {
+                        for expr in exprs {
+                            expr.respan(span);
+                        }
+                    }
+                }
+            }
+            Expr::RawCss(raw_css) => raw_css.span = span,
+        }
+    }
+}
+
+/// `(_ @md hover)`, `_` is `None`
+#[derive(Debug, Clone)]
+pub struct VariantGroup {
+    pub variants: Vec<Spanned<Option<Variant>>>,
+    pub span: Span,
 }
 
 #[derive(Debug)]
 pub struct Util {
-    pub properties: Vec<String>,
+    pub properties: Vec<Spanned<String>>,
     pub tree: UtilTree,
+    pub span: Span,
 }
 
 #[derive(Debug)]
 pub enum UtilTree {
-    Leaf(Option<ValueOrGroup>),
+    Leaf(Option<Spanned<ValueOrGroup>>),
     Branch(Vec<Expr>),
 }
 
@@ -27,10 +89,20 @@ pub enum Variant {
     Media(Value),
 }
 
-#[derive(Debug, Hash, Clone, PartialEq, Eq, PartialOrd, Ord)]
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Variant::Pseudo(value) => value.fmt(f),
+            Variant::Media(value) => write!(f, "@{}", value),
+        }
+    }
+}
+
+/// A value group's `_` is `None`
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
 pub enum ValueOrGroup {
     Value(Value),
-    Group(Vec<Option<Value>>),
+    Group(Vec<Spanned<Option<Value>>>),
 }
 
 #[derive(Debug, Hash, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -38,3 +110,12 @@ pub enum Value {
     Iden(String),
     Raw(String),
 }
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Iden(iden) => f.write_str(iden),
+            Value::Raw(raw) => write!(f, "[{}]", raw),
+        }
+    }
+}
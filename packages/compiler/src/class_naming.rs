@@ -0,0 +1,159 @@
+/// How classes are named
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClassNaming {
+    /// `_E0`, `_E1`, ... with `prefix: "_E"`
+    Counter { prefix: String },
+
+    /// `_0`, ..., `_Z`, `_10`, ... with `prefix: "_"`, shorter than `Counter`
+    Base62 { prefix: String },
+
+    /// `_3kTMd0e8` with `prefix: "_"`, a hash of the util so the name stays the
+    /// same across builds
+    ContentHash { prefix: String },
+
+    /// `md_hover_bg-c_blue-500`, for development
+    Readable,
+}
+
+impl Default for ClassNaming {
+    fn default() -> Self {
+        ClassNaming::Counter {
+            prefix: String::from("_E"),
+        }
+    }
+}
+
+impl ClassNaming {
+    /// Name of the `index`th class, `source` being its util written as ecss
+    pub(crate) fn name(&self, index: u32, source: &str) -> String {
+        match self {
+            ClassNaming::Counter { prefix } => format!("{}{}", prefix, index),
+            ClassNaming::Base62 { prefix } => format!("{}{}", prefix, base62(index.into())),
+            ClassNaming::ContentHash { prefix } => {
+                // Always 8 digits, zero padded
+                format!("{}{:0>8}", prefix, base62(fnv1a(source) % 62u64.pow(8)))
+            }
+            ClassNaming::Readable => source
+                .chars()
+                .filter(|char| *char != '@')
+                .map(|char| match char {
+                    ':' | '=' => '_',
+                    char if char.is_whitespace() => '_',
+                    char => char,
+                })
+                .collect(),
+        }
+    }
+}
+
+fn base62(mut number: u64) -> String {
+    const DIGITS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    let mut digits = Vec::new();
+    loop {
+        digits.push(DIGITS[(number % 62) as usize]);
+        number /= 62;
+        if number == 0 {
+            break;
+        }
+    }
+    digits.iter().rev().map(|digit| *digit as char).collect()
+}
+
+/// FNV-1a, unlike `DefaultHasher` it's guaranteed to never change
+fn fnv1a(str: &str) -> u64 {
+    str.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Escapes a class name for use in a selector, like `CSS.escape`
+pub(crate) fn escape(name: &str) -> String {
+    let mut escaped = String::new();
+    for (index, char) in name.chars().enumerate() {
+        match char {
+            '\0' => escaped.push('\u{FFFD}'),
+            '\u{1}'..='\u{1f}' | '\u{7f}' => escaped.push_str(&format!("\\{:x} ", char as u32)),
+            '0'..='9' if index == 0 || index == 1 && name.starts_with('-') => {
+                escaped.push_str(&format!("\\{:x} ", char as u32))
+            }
+            '-' if index == 0 && name.len() == 1 => escaped.push_str("\\-"),
+            char if char.is_ascii_alphanumeric() || char == '-' || char == '_' => {
+                escaped.push(char)
+            }
+            char if !char.is_ascii() => escaped.push(char),
+            char => {
+                escaped.push('\\');
+                escaped.push(char);
+            }
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerOptions};
+
+    #[test]
+    fn counter() {
+        let naming = ClassNaming::default();
+        assert_eq!(naming.name(0, "p=1"), "_E0");
+        assert_eq!(naming.name(62, "p=1"), "_E62");
+    }
+
+    #[test]
+    fn base62_boundaries() {
+        let naming = ClassNaming::Base62 {
+            prefix: String::from("_"),
+        };
+        assert_eq!(naming.name(0, "p=1"), "_0");
+        assert_eq!(naming.name(61, "p=1"), "_Z");
+        assert_eq!(naming.name(62, "p=1"), "_10");
+        assert_eq!(naming.name(62 * 62, "p=1"), "_100");
+    }
+
+    #[test]
+    fn content_hash_only_depends_on_the_util() {
+        let naming = ClassNaming::ContentHash {
+            prefix: String::from("_"),
+        };
+        assert_eq!(naming.name(0, "p=1"), "_XnZDoyln");
+        assert_eq!(naming.name(7, "p=1"), "_XnZDoyln");
+        assert_eq!(naming.name(0, "@md:hover:bg-c=blue-500"), "_VT3J4zgo");
+        assert_eq!(naming.name(0, "e"), "_0Hv8GwG4");
+    }
+
+    #[test]
+    fn escapes_selectors() {
+        assert_eq!(escape("md_hover_bg-c_blue-500"), "md_hover_bg-c_blue-500");
+        assert_eq!(escape("p=1:hover"), "p\\=1\\:hover");
+        assert_eq!(escape("w_[calc(1px+2px)]"), "w_\\[calc\\(1px\\+2px\\)\\]");
+        assert_eq!(escape("2xl_p_1"), "\\32 xl_p_1");
+        assert_eq!(escape("-2"), "-\\32 ");
+        assert_eq!(escape("-"), "\\-");
+    }
+
+    #[test]
+    fn readable_names_are_escaped_in_css() {
+        let mut compiler = Compiler::with_options(CompilerOptions {
+            class_naming: ClassNaming::Readable,
+            ..Default::default()
+        });
+        assert_eq!(
+            compiler
+                .generate_classes("@2xl:p=[1px] hover:m=2 w=[calc(1px+2px)]")
+                .unwrap(),
+            ["2xl_p_[1px]", "hover_m_2", "w_[calc(1px+2px)]"]
+        );
+        assert_eq!(
+            compiler.get_css(),
+            concat!(
+                ".w_\\[calc\\(1px\\+2px\\)\\]{width:calc(1px+2px);}",
+                ".hover_m_2:hover{margin:0.5rem;}",
+                "@media (min-width:1536px){.\\32 xl_p_\\[1px\\]{padding:1px;}}",
+            )
+        );
+    }
+}
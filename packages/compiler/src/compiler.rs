@@ -1,25 +1,159 @@
+pub use crate::ast::Value;
+pub use crate::class_naming::ClassNaming;
+pub use crate::css_writer::ClassDescription;
 use crate::css_writer::CssWriter;
+use crate::error::CompileError;
 use crate::lexer;
+use crate::manifest::Manifest;
 use crate::parser::Parser;
+use crate::source_map::SourceOrigin;
+use crate::theme::Theme;
+pub use crate::utils::registry::{
+    Declaration, DeclarationValue, KnownValuesFn, NamedValueFn, Utility, UtilityPlugin,
+    UtilityRegistry, ValueFn, ValueFns,
+};
+
+/// What to do with the classes of code that has errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorPolicy {
+    /// Fail the whole compile, nothing is registered
+    #[default]
+    Strict,
+
+    /// Drop the bad utils and keep the rest
+    Lenient,
+
+    /// Like `Lenient`, but the errors are kept as warnings
+    Warn,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputStyle {
+    /// Everything on one line, for production
+    #[default]
+    Minified,
+
+    /// Indented with one declaration per line, for reading and diffing
+    Pretty,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CompilerOptions {
+    pub error_policy: ErrorPolicy,
+    pub output_style: OutputStyle,
+
+    /// Precede every rule with a comment of the ecss that generated it
+    pub source_comments: bool,
+
+    pub class_naming: ClassNaming,
+
+    /// Colors, spacing, breakpoints and other named values
+    pub theme: Theme,
+
+    /// The utils that can be used, the built-in ones by default. Register
+    /// plugins here before creating the `Compiler`
+    pub utilities: UtilityRegistry,
+}
 
 pub struct Compiler {
     css_writer: CssWriter,
+    options: CompilerOptions,
+    warnings: Vec<CompileError>,
 }
 
 impl Compiler {
     pub fn new() -> Self {
+        Self::with_options(CompilerOptions::default())
+    }
+
+    pub fn with_options(options: CompilerOptions) -> Self {
         Self {
-            css_writer: CssWriter::new(),
+            css_writer: CssWriter::new(options.class_naming.clone()),
+            options,
+            warnings: Vec::new(),
         }
     }
 
-    pub fn generate_classes(&mut self, code: &str) -> Vec<String> {
-        let tokens = lexer::get_tokens(code);
-        let ast = Parser::from(tokens).parse();
-        self.css_writer.generate_classes(&ast)
+    /// Every error in `code` is reported at once. Only `ErrorPolicy::Strict`
+    /// returns them, the other policies register the valid utils and return
+    /// their classes.
+    pub fn generate_classes(&mut self, code: &str) -> Result<Vec<String>, Vec<CompileError>> {
+        self.generate(code, None)
+    }
+
+    /// Like `generate_classes`, with `origin` being where `code` starts in its
+    /// file. New classes are mapped back to it by `get_css_with_source_map`
+    pub fn generate_classes_at(
+        &mut self,
+        code: &str,
+        origin: &SourceOrigin,
+    ) -> Result<Vec<String>, Vec<CompileError>> {
+        self.generate(code, Some(origin))
+    }
+
+    fn generate(
+        &mut self,
+        code: &str,
+        origin: Option<&SourceOrigin>,
+    ) -> Result<Vec<String>, Vec<CompileError>> {
+        let (tokens, lex_errors) = lexer::get_tokens(code);
+        let (ast, parse_errors) = Parser::from(tokens).parse();
+        let (resolved_entries, css_errors) = self.css_writer.resolve(&ast, &self.options);
+
+        let errors: Vec<CompileError> = lex_errors
+            .into_iter()
+            .map(CompileError::from)
+            .chain(parse_errors.into_iter().map(CompileError::from))
+            .chain(css_errors.into_iter().map(CompileError::from))
+            .collect();
+        match self.options.error_policy {
+            ErrorPolicy::Strict if !errors.is_empty() => return Err(errors),
+            ErrorPolicy::Warn => self.warnings.extend(errors),
+            _ => {}
+        }
+
+        Ok(self.css_writer.register(resolved_entries, code, origin))
+    }
+
+    /// Errors kept by `ErrorPolicy::Warn` since the last call, rendered with
+    /// `Diagnostic::warning`
+    pub fn take_warnings(&mut self) -> Vec<CompileError> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// All utils that can be used, for completion and docs
+    pub fn utilities(&self) -> &UtilityRegistry {
+        &self.options.utilities
+    }
+
+    /// The util behind a generated class, `None` if no class has that name
+    pub fn describe_class(&self, name: &str) -> Option<ClassDescription> {
+        self.css_writer.describe_class(name)
+    }
+
+    /// Save it and `load_manifest` it in the next build to keep the same names
+    pub fn manifest(&self) -> Manifest {
+        self.css_writer.manifest()
+    }
+
+    /// Load before generating classes, so known utils get their old names
+    pub fn load_manifest(&mut self, manifest: Manifest) {
+        self.css_writer.load_manifest(manifest)
     }
 
     pub fn get_css(&mut self) -> String {
-        self.css_writer.get_css()
+        self.css_writer.get_css(&self.options)
+    }
+
+    /// The CSS and its Source Map v3. `css_file` is the name the CSS is saved as
+    pub fn get_css_with_source_map(&mut self, css_file: &str) -> (String, String) {
+        self.css_writer
+            .get_css_with_source_map(&self.options, css_file)
+    }
+}
+
+impl Default for Compiler {
+    fn default() -> Self {
+        Self::new()
     }
 }
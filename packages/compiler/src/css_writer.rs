@@ -1,21 +1,104 @@
-use std::collections::HashMap;
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    fmt,
+};
 
 use crate::{
     ast::*,
-    utils::{get_util_css, media_queries::get_media_query, pseudo_classes::get_pseudo},
+    class_naming::{escape, ClassNaming},
+    compiler::{CompilerOptions, OutputStyle},
+    lexer,
+    manifest::{Manifest, ManifestClass},
+    parser::Parser,
+    source_map::{locate, source_map, SourceOrigin, SourcePosition},
+    span::{Span, Spanned},
+    theme::{lookup, Scale},
+    utils::{
+        media_queries::{get_media_query, max_width, min_width},
+        pseudo_classes::{get_pseudo, is_pseudo_element},
+        UtilError,
+    },
 };
 
 type ClassName = String;
 
 pub struct CssWriter {
-    class_names: HashMap<CssEntry, ClassName>,
+    class_names: HashMap<CssEntry, Class>,
     class_count: u32,
+    naming: ClassNaming,
+
+    /// Names can collide when they're derived from the util
+    used_names: HashSet<ClassName>,
+
+    /// Index and name of classes from a loaded `Manifest`, by source
+    manifest_classes: HashMap<String, (u32, ClassName)>,
+
+    /// Files of the `SourceOrigin`s classes were registered from
+    source_files: Vec<String>,
 }
 
+/// A registered `CssEntry`
+#[derive(Debug)]
+struct Class {
+    name: ClassName,
+
+    /// Order of registration, breaks ties in the cascade
+    index: u32,
+
+    /// The entry written as ecss, `@md:hover:bg-c=blue-500`
+    source: String,
+
+    /// Where the entry was first registered from, if the origin was known
+    origin: Option<SourcePosition>,
+
+    rule: Rule,
+}
+
+/// Spans point back to the source of the entry but don't affect deduplication
 #[derive(Debug, PartialEq, Eq, Hash)]
 struct CssEntry {
-    variants: Option<Vec<Variant>>,
-    css: FlatUtilOrRaw,
+    variants: Option<Vec<Spanned<Variant>>>,
+    css: Spanned<FlatUtilOrRaw>,
+}
+
+impl CssEntry {
+    /// The property of a util, or the raw CSS
+    fn source_span(&self) -> Span {
+        match &self.css.node {
+            FlatUtilOrRaw::FlatUtil(util) => util.prop.span,
+            FlatUtilOrRaw::RawCss(_) => self.css.span,
+        }
+    }
+
+    /// Media queries first, like they are usually written
+    fn ordered_variants(&self) -> impl Iterator<Item = &Variant> {
+        let (media, pseudo): (Vec<_>, Vec<_>) = self
+            .variants
+            .iter()
+            .flatten()
+            .map(|variant| &variant.node)
+            .partition(|variant| matches!(variant, Variant::Media(_)));
+        media.into_iter().chain(pseudo)
+    }
+}
+
+impl fmt::Display for CssEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for variant in self.ordered_variants() {
+            write!(f, "{}:", variant)?;
+        }
+        match &self.css.node {
+            FlatUtilOrRaw::FlatUtil(util) => {
+                f.write_str(&util.prop)?;
+                if let Some(value) = &util.value {
+                    write!(f, "={}", value.node)?;
+                }
+                Ok(())
+            }
+            FlatUtilOrRaw::RawCss(raw) => write!(f, "[{}]", raw),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Hash)]
@@ -25,216 +108,635 @@ enum FlatUtilOrRaw {
 }
 #[derive(Debug, PartialEq, Eq, Hash)]
 struct FlatUtil {
-    prop: String,
-    value: Option<Value>,
+    prop: Spanned<String>,
+    value: Option<Spanned<Value>>,
 }
 
+/// What a generated class is made of
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassDescription {
+    pub name: String,
+
+    /// `["@md", "hover"]`
+    pub variants: Vec<String>,
+
+    /// `bg-c`, `None` for raw CSS
+    pub property: Option<String>,
+
+    /// `blue-500`, the CSS for raw CSS
+    pub value: Option<String>,
+
+    /// `@media (min-width:768px){._E1:hover{background-color:#3b82f6;}}`
+    pub css: String,
+
+    /// `@md:hover:bg-c=blue-500`
+    pub source: String,
+}
+
+/// A resolved `CssEntry`
+#[derive(Debug)]
+struct Rule {
+    /// `:hover::before`
+    pseudo_selectors: String,
+
+    /// `["(min-width:768px)", "print"]`
+    media_queries: Vec<String>,
+
+    /// `padding:1rem;`
+    declarations: String,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum CssErrorKind {
+    Util(UtilError),
+
+    /// `(_ @md):p=(1 2 3)`
+    GroupLengthMismatch {
+        variants: usize,
+        values: usize,
+        variant_group: Span,
+    },
+
+    /// `p=(1 2)` with no variant group to match against
+    MissingVariantGroup {
+        values: usize,
+    },
+
+    /// The ecss of a shortcut doesn't parse, `error` is its first error
+    InvalidShortcut {
+        name: String,
+        error: String,
+    },
+
+    /// A shortcut that uses itself
+    RecursiveShortcut {
+        name: String,
+    },
+}
+
+impl fmt::Display for CssErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CssErrorKind::Util(err) => err.fmt(f),
+            CssErrorKind::GroupLengthMismatch {
+                variants, values, ..
+            } => write!(
+                f,
+                "Value group has {} but variant group has {}",
+                plural(*values, "value"),
+                plural(*variants, "variant")
+            ),
+            CssErrorKind::MissingVariantGroup { values } => write!(
+                f,
+                "Value group has {} but there is no variant group",
+                plural(*values, "value")
+            ),
+            CssErrorKind::InvalidShortcut { name, error } => {
+                write!(f, "Invalid shortcut {}: {}", name, error)
+            }
+            CssErrorKind::RecursiveShortcut { name } => {
+                write!(f, "Shortcut {} uses itself", name)
+            }
+        }
+    }
+}
+
+fn plural(count: usize, word: &str) -> String {
+    if count == 1 {
+        format!("{} {}", count, word)
+    } else {
+        format!("{} {}s", count, word)
+    }
+}
+
+/// A `CssEntry` ready to be registered. `rule` is `None` when the entry was
+/// already registered at resolve time
+pub struct ResolvedEntry {
+    entry: CssEntry,
+    rule: Option<Rule>,
+}
+
+/// State of flattening one piece of code
+struct FlattenContext<'a> {
+    shortcuts: &'a Scale,
+
+    /// Names of the shortcuts being expanded, innermost last
+    expanding: Vec<String>,
+
+    errors: Vec<CssError>,
+}
+
+/// Group errors point at the value group
+#[derive(Debug, PartialEq, Clone)]
+pub struct CssError {
+    pub kind: CssErrorKind,
+    pub span: Span,
+}
+
+impl fmt::Display for CssError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.kind.fmt(f)
+    }
+}
+
+impl std::error::Error for CssError {}
+
 impl CssWriter {
-    pub fn new() -> Self {
+    pub fn new(naming: ClassNaming) -> Self {
         Self {
             class_names: HashMap::new(),
             class_count: 0,
+            naming,
+            used_names: HashSet::new(),
+            manifest_classes: HashMap::new(),
+            source_files: Vec::new(),
         }
     }
 
-    fn new_class_name(&mut self) -> String {
-        let class_name = format!("_E{}", self.class_count);
+    fn new_class_name(&mut self, source: &str) -> String {
+        let name = self.naming.name(self.class_count, source);
+        let mut class_name = name.clone();
+        let mut suffix = 2;
+        while self.used_names.contains(&class_name) {
+            class_name = format!("{}-{}", name, suffix);
+            suffix += 1;
+        }
+        self.used_names.insert(class_name.clone());
         self.class_count += 1;
         class_name
     }
 
-    pub fn get_css(&mut self) -> String {
-        let mut css: Vec<String> = self
-            .class_names
-            .iter()
-            .map(|(entry, class_name)| {
-                let (pseudo, media) =
-                    entry
-                        .variants
-                        .iter()
-                        .fold((Vec::new(), Vec::new()), |acc, cur| {
-                            let (mut pseudo, mut media) = acc;
-                            for variant in cur {
-                                match variant {
-                                    Variant::Pseudo(p) => {
-                                        pseudo.push(p);
-                                    }
-                                    Variant::Media(m) => {
-                                        media.push(m);
-                                    }
-                                }
-                            }
-                            (pseudo, media)
-                        });
-                let mut pseudo_selectors = String::new();
-                for p in pseudo {
-                    match p {
-                        Value::Iden(iden) => {
-                            pseudo_selectors.push_str(
-                                &get_pseudo(iden).expect(&format!("Unknown pseudo: {}", iden)),
-                            );
-                        }
-                        Value::Raw(raw) => {
-                            pseudo_selectors.push_str(&format!(":{}", raw));
-                        }
-                    }
+    pub fn get_css(&self, options: &CompilerOptions) -> String {
+        self.write_css(options).0
+    }
+
+    /// The CSS and its Source Map v3, mapping every selector to the util that
+    /// generated it
+    pub fn get_css_with_source_map(
+        &self,
+        options: &CompilerOptions,
+        css_file: &str,
+    ) -> (String, String) {
+        let (css, mappings) = self.write_css(options);
+        let source_map = source_map(css_file, &css, &self.source_files, &mappings);
+        (css, source_map)
+    }
+
+    /// Rules with the same media queries share one `@media` block, and classes
+    /// with the same rule share one selector list. Also returns where each
+    /// selector with a known origin starts
+    fn write_css(&self, options: &CompilerOptions) -> (String, Vec<(usize, SourcePosition)>) {
+        let mut classes: Vec<&Class> = self.class_names.values().collect();
+        classes.sort_by(|a, b| cascade_order(a, b));
+
+        let pretty = options.output_style == OutputStyle::Pretty;
+        let mut css = String::new();
+        let mut mappings = Vec::new();
+        let mut media_queries: &[String] = &[];
+        for (index, classes) in merge_rules(classes).into_iter().enumerate() {
+            let rule = &classes[0].rule;
+            if rule.media_queries != media_queries && !media_queries.is_empty() {
+                css.push_str(if pretty { "}\n" } else { "}" });
+            }
+            if index > 0 && pretty {
+                css.push('\n');
+            }
+            if rule.media_queries != media_queries {
+                if !rule.media_queries.is_empty() {
+                    css.push_str(&format!(
+                        "@media {}{}",
+                        rule.media_queries.join(" and "),
+                        if pretty { " {\n" } else { "{" }
+                    ));
                 }
-                let mut css = match &entry.css {
-                    FlatUtilOrRaw::FlatUtil(util) => format!(
-                        ".{}{}{{{}}}",
-                        class_name,
-                        pseudo_selectors,
-                        get_util_css(&util.prop, &util.value)
-                    ),
-                    FlatUtilOrRaw::RawCss(raw) => {
-                        format!(".{}{}{{{};}}", class_name, pseudo_selectors, raw)
-                    }
-                };
+                media_queries = &rule.media_queries;
+            }
+            let indent = if pretty && !media_queries.is_empty() {
+                "  "
+            } else {
+                ""
+            };
+            if options.source_comments {
+                let sources: Vec<String> = classes
+                    .iter()
+                    .map(|class| class.source.replace("*/", "* /"))
+                    .collect();
+                css.push_str(&format!(
+                    "{}/* {} */{}",
+                    indent,
+                    sources.join(", "),
+                    if pretty { "\n" } else { "" }
+                ));
+            }
+            for (index, class) in classes.iter().enumerate() {
+                match (index, pretty) {
+                    (0, _) => css.push_str(indent),
+                    (_, true) => css.push_str(&format!(",\n{}", indent)),
+                    (_, false) => css.push(','),
+                }
+                if let Some(origin) = class.origin {
+                    mappings.push((css.len(), origin));
+                }
+                css.push_str(&format!(
+                    ".{}{}",
+                    escape(&class.name),
+                    rule.pseudo_selectors
+                ));
+            }
+            if pretty {
+                css.push_str(" {\n");
+                for declaration in split_declarations(&rule.declarations) {
+                    let declaration = match declaration.split_once(':') {
+                        Some((property, value)) => format!("{}: {}", property.trim(), value.trim()),
+                        None if declaration.trim().is_empty() => continue,
+                        None => declaration.trim().to_string(),
+                    };
+                    css.push_str(&format!("{}  {};\n", indent, declaration));
+                }
+                css.push_str(&format!("{}}}\n", indent));
+            } else {
+                css.push_str(&format!("{{{}}}", rule.declarations));
+            }
+        }
+        if !media_queries.is_empty() {
+            css.push_str(if pretty { "}\n" } else { "}" });
+        }
 
-                if !media.is_empty() {
-                    let media_queries: Vec<String> = media
-                        .iter()
-                        .map(|m| match m {
-                            Value::Iden(iden) => get_media_query(iden).unwrap(),
-                            Value::Raw(raw) => raw.clone(),
-                        })
-                        .collect();
+        (css, mappings)
+    }
+
+    /// Resolves the entries of `ast` without registering them. Entries that
+    /// can't be resolved are left out and returned as errors
+    pub fn resolve(
+        &self,
+        ast: &[Expr],
+        options: &CompilerOptions,
+    ) -> (Vec<ResolvedEntry>, Vec<CssError>) {
+        let mut resolved_entries = Vec::new();
+        let mut cx = FlattenContext {
+            shortcuts: &options.theme.shortcuts,
+            expanding: Vec::new(),
+            errors: Vec::new(),
+        };
 
-                    css = format!("@media {}{{{}}}", media_queries.join(" and "), css)
+        let css_entries: Vec<CssEntry> = ast
+            .iter()
+            .flat_map(|expr| self.flatten_expr(expr, Vec::new(), None, String::new(), &mut cx))
+            .collect();
+        let mut errors = cx.errors;
+        for css_entry in css_entries {
+            if self.class_names.contains_key(&css_entry) {
+                resolved_entries.push(ResolvedEntry {
+                    entry: css_entry,
+                    rule: None,
+                });
+                continue;
+            }
+            match self.resolve_entry(&css_entry, options) {
+                Ok(rule) => resolved_entries.push(ResolvedEntry {
+                    entry: css_entry,
+                    rule: Some(rule),
+                }),
+                Err(err) => errors.push(err),
+            }
+        }
+
+        (resolved_entries, errors)
+    }
+
+    /// Gives every entry a class name, reusing the existing one for known entries.
+    /// `origin` is where `code` starts, for source maps
+    pub fn register(
+        &mut self,
+        resolved_entries: Vec<ResolvedEntry>,
+        code: &str,
+        origin: Option<&SourceOrigin>,
+    ) -> Vec<String> {
+        let source = origin.map(|origin| {
+            match self
+                .source_files
+                .iter()
+                .position(|file| *file == origin.file)
+            {
+                Some(source) => source,
+                None => {
+                    self.source_files.push(origin.file.clone());
+                    self.source_files.len() - 1
+                }
+            }
+        });
+        resolved_entries
+            .into_iter()
+            .filter_map(|ResolvedEntry { entry, rule }| {
+                if let Some(class) = self.class_names.get(&entry) {
+                    return Some(class.name.clone());
                 }
+                let rule = rule?;
+                let origin = origin
+                    .zip(source)
+                    .map(|(origin, source)| locate(code, entry.source_span(), origin, source));
+                let source = entry.to_string();
+                let (index, name) = match self.manifest_classes.get(&source) {
+                    Some((index, name)) => (*index, name.clone()),
+                    None => (self.class_count, self.new_class_name(&source)),
+                };
+                self.class_names.insert(
+                    entry,
+                    Class {
+                        name: name.clone(),
+                        index,
+                        source,
+                        origin,
+                        rule,
+                    },
+                );
+                Some(name)
+            })
+            .collect()
+    }
+
+    pub fn describe_class(&self, name: &str) -> Option<ClassDescription> {
+        let (entry, class) = self
+            .class_names
+            .iter()
+            .find(|(_, class)| class.name == name)?;
+        let (property, value) = match &entry.css.node {
+            FlatUtilOrRaw::FlatUtil(util) => (
+                Some(util.prop.node.clone()),
+                util.value.as_ref().map(|value| value.node.to_string()),
+            ),
+            FlatUtilOrRaw::RawCss(raw) => (None, Some(raw.clone())),
+        };
+        let rule = &class.rule;
+        let css = format!(
+            ".{}{}{{{}}}",
+            escape(&class.name),
+            rule.pseudo_selectors,
+            rule.declarations
+        );
 
+        Some(ClassDescription {
+            name: class.name.clone(),
+            variants: entry
+                .ordered_variants()
+                .map(|variant| variant.to_string())
+                .collect(),
+            property,
+            value,
+            css: if rule.media_queries.is_empty() {
                 css
+            } else {
+                format!("@media {}{{{}}}", rule.media_queries.join(" and "), css)
+            },
+            source: class.source.clone(),
+        })
+    }
+
+    /// Classes registered so far, and those of the loaded manifest
+    pub fn manifest(&self) -> Manifest {
+        let mut classes: HashMap<&str, (u32, &str)> = self
+            .manifest_classes
+            .iter()
+            .map(|(source, (index, name))| (source.as_str(), (*index, name.as_str())))
+            .collect();
+        for class in self.class_names.values() {
+            classes.insert(&class.source, (class.index, &class.name));
+        }
+        let mut classes: Vec<ManifestClass> = classes
+            .into_iter()
+            .map(|(source, (index, name))| ManifestClass {
+                index,
+                name: name.to_string(),
+                source: source.to_string(),
             })
             .collect();
+        classes.sort_by_key(|class| class.index);
 
-        css.sort();
-        css.join("")
+        Manifest {
+            class_count: self.class_count,
+            classes,
+        }
     }
 
-    pub fn generate_classes(&mut self, ast: &[Expr]) -> Vec<String> {
-        let mut class_names = Vec::new();
+    /// Utils in the manifest get their old name and index once registered.
+    /// Classes registered before loading keep their names, manifest classes
+    /// that clash with them are dropped
+    pub fn load_manifest(&mut self, manifest: Manifest) {
+        self.class_count = self.class_count.max(manifest.class_count);
+        for class in manifest.classes {
+            let registered = self
+                .class_names
+                .values()
+                .any(|registered| registered.source == class.source);
+            if registered || self.used_names.contains(&class.name) {
+                continue;
+            }
+            self.used_names.insert(class.name.clone());
+            self.manifest_classes
+                .insert(class.source, (class.index, class.name));
+        }
+    }
 
-        let css_entries: Vec<CssEntry> = ast
-            .iter()
-            .flat_map(|expr| self.flatten_expr(expr, Vec::new(), Vec::new(), String::new()))
-            .collect();
-        for css_entry in css_entries {
-            match self.class_names.get(&css_entry) {
-                Some(class_name) => {
-                    class_names.push(class_name.clone());
+    fn resolve_entry(&self, entry: &CssEntry, options: &CompilerOptions) -> Result<Rule, CssError> {
+        let mut pseudo_selectors = String::new();
+        let mut media_queries = Vec::new();
+        for variant in entry.variants.iter().flatten() {
+            match &variant.node {
+                Variant::Pseudo(Value::Iden(iden)) => {
+                    pseudo_selectors.push_str(&get_pseudo(iden).map_err(|err| CssError {
+                        kind: CssErrorKind::Util(err),
+                        span: variant.span,
+                    })?);
                 }
-                None => {
-                    let class_name = self.new_class_name();
-                    self.class_names.insert(css_entry, class_name.clone());
-                    class_names.push(class_name);
+                Variant::Pseudo(Value::Raw(raw)) => {
+                    pseudo_selectors.push_str(&format!(":{}", raw));
                 }
-            };
+                Variant::Media(Value::Iden(iden)) => {
+                    media_queries.push(get_media_query(iden, &options.theme).map_err(|err| {
+                        CssError {
+                            kind: CssErrorKind::Util(err),
+                            span: variant.span,
+                        }
+                    })?);
+                }
+                Variant::Media(Value::Raw(raw)) => {
+                    media_queries.push(raw.clone());
+                }
+            }
         }
 
-        class_names
+        let declarations = match &entry.css.node {
+            FlatUtilOrRaw::FlatUtil(util) => options
+                .utilities
+                .util_css(&util.prop, util.value.as_deref(), &options.theme)
+                .map_err(|err| {
+                    let span = match (&err, &util.value) {
+                        (
+                            UtilError::InvalidValue { .. } | UtilError::Plugin { .. },
+                            Some(value),
+                        ) => value.span,
+                        _ => entry.css.span,
+                    };
+                    CssError {
+                        kind: CssErrorKind::Util(err),
+                        span,
+                    }
+                })?,
+            FlatUtilOrRaw::RawCss(raw) => format!("{};", raw),
+        };
+
+        Ok(Rule {
+            pseudo_selectors,
+            media_queries,
+            declarations,
+        })
     }
 
+    /// Value groups match against the variant group of the closest expression
+    /// that has one, so `(_ @md):(hover:p=(1 2))` works. A nested variant group
+    /// replaces the outer one.
+    ///
+    /// Utils with a value group that doesn't match are skipped and reported
     fn flatten_expr(
         &self,
         expr: &Expr,
-        mut variants: Vec<Variant>,
-        variant_group: Vec<Option<Variant>>,
+        mut variants: Vec<Spanned<Variant>>,
+        variant_group: Option<VariantGroup>,
         prev_prop: String,
+        cx: &mut FlattenContext,
     ) -> Vec<CssEntry> {
         match expr {
-            Expr::Util(util) => util
-                .properties
-                .iter()
-                .flat_map(|prop| match &util.tree {
-                    UtilTree::Leaf(value) => {
-                        match value {
-                            Some(ValueOrGroup::Group(values)) => {
-                                assert_eq!(values.len(), variant_group.len());
-
-                                let mut entries = Vec::new();
-
-                                for (index, value) in values.iter().enumerate() {
-                                    match value {
-                                        Some(value) => {
-                                            let mut variants = variants.clone();
-                                            if let Some(group_variant) =
-                                                variant_group.get(index).unwrap()
-                                            {
-                                                variants.push(group_variant.clone());
+            Expr::Util(util) => {
+                if let UtilTree::Leaf(Some(Spanned {
+                    node: ValueOrGroup::Group(values),
+                    span,
+                })) = &util.tree
+                {
+                    if let Err(err) = check_value_group(values.len(), *span, variant_group.as_ref())
+                    {
+                        cx.errors.push(err);
+                        return Vec::new();
+                    }
+                }
+
+                util.properties
+                    .iter()
+                    .flat_map(|prop| {
+                        let flat_prop =
+                            Spanned::new(format!("{}{}", prev_prop, prop.node), prop.span);
+                        match &util.tree {
+                            UtilTree::Leaf(value) => {
+                                match value.as_ref().map(|value| &value.node) {
+                                    Some(ValueOrGroup::Group(values)) => {
+                                        let group_variants = variant_group
+                                            .as_ref()
+                                            .map_or(&[][..], |group| &group.variants[..]);
+
+                                        let mut entries = Vec::new();
+
+                                        for (index, value) in values.iter().enumerate() {
+                                            if let Some(node) = &value.node {
+                                                let mut variants = variants.clone();
+                                                let group_variant = &group_variants[index];
+                                                if let Some(node) = &group_variant.node {
+                                                    variants.push(Spanned::new(
+                                                        node.clone(),
+                                                        group_variant.span,
+                                                    ));
+                                                }
+                                                sort_variants(&mut variants);
+                                                entries.push(CssEntry {
+                                                    variants: Some(variants),
+                                                    css: Spanned::new(
+                                                        FlatUtilOrRaw::FlatUtil(FlatUtil {
+                                                            prop: flat_prop.clone(),
+                                                            value: Some(Spanned::new(
+                                                                node.clone(),
+                                                                value.span,
+                                                            )),
+                                                        }),
+                                                        util.span,
+                                                    ),
+                                                })
                                             }
-                                            // Sort so order won't change hash
-                                            variants.sort();
-                                            entries.push(CssEntry {
-                                                variants: Some(variants),
-                                                css: FlatUtilOrRaw::FlatUtil(FlatUtil {
-                                                    prop: format!("{}{}", prev_prop, prop),
-                                                    value: Some(value.clone()),
-                                                }),
-                                            })
                                         }
-                                        None => {}
+
+                                        entries
+                                    }
+                                    Some(ValueOrGroup::Value(node)) => {
+                                        vec![CssEntry {
+                                            variants: if variants.is_empty() {
+                                                None
+                                            } else {
+                                                let mut variants = variants.clone();
+                                                sort_variants(&mut variants);
+                                                Some(variants)
+                                            },
+                                            css: Spanned::new(
+                                                FlatUtilOrRaw::FlatUtil(FlatUtil {
+                                                    prop: flat_prop,
+                                                    value: value.as_ref().map(|value| {
+                                                        Spanned::new(node.clone(), value.span)
+                                                    }),
+                                                }),
+                                                util.span,
+                                            ),
+                                        }]
+                                    }
+                                    None if lookup(cx.shortcuts, &flat_prop.node).is_some() => self
+                                        .flatten_shortcut(
+                                            &flat_prop,
+                                            variants.clone(),
+                                            variant_group.clone(),
+                                            cx,
+                                        ),
+                                    None => {
+                                        vec![CssEntry {
+                                            variants: if variants.is_empty() {
+                                                None
+                                            } else {
+                                                let mut variants = variants.clone();
+                                                sort_variants(&mut variants);
+                                                Some(variants)
+                                            },
+                                            css: Spanned::new(
+                                                FlatUtilOrRaw::FlatUtil(FlatUtil {
+                                                    prop: flat_prop,
+                                                    value: None,
+                                                }),
+                                                util.span,
+                                            ),
+                                        }]
                                     }
                                 }
-
-                                entries
-                            }
-                            Some(ValueOrGroup::Value(value)) => {
-                                vec![CssEntry {
-                                    variants: if variants.is_empty() {
-                                        None
-                                    } else {
-                                        let mut variants = variants.clone();
-                                        // Sort so order won't change hash
-                                        variants.sort();
-                                        Some(variants)
-                                    },
-                                    css: FlatUtilOrRaw::FlatUtil(FlatUtil {
-                                        prop: format!("{}{}", prev_prop, prop),
-                                        value: Some(value.clone()),
-                                    }),
-                                }]
-                            }
-                            None => {
-                                vec![CssEntry {
-                                    variants: if variants.is_empty() {
-                                        None
-                                    } else {
-                                        let mut variants = variants.clone();
-                                        // Sort so order won't change hash
-                                        variants.sort();
-                                        Some(variants)
-                                    },
-                                    css: FlatUtilOrRaw::FlatUtil(FlatUtil {
-                                        prop: format!("{}{}", prev_prop, prop),
-                                        value: None,
-                                    }),
-                                }]
                             }
+                            UtilTree::Branch(exprs) => exprs
+                                .iter()
+                                .flat_map(|expr| {
+                                    self.flatten_expr(
+                                        expr,
+                                        variants.clone(),
+                                        variant_group.clone(),
+                                        flat_prop.node.clone(),
+                                        cx,
+                                    )
+                                })
+                                .collect(),
                         }
-                    }
-                    UtilTree::Branch(exprs) => exprs
-                        .iter()
-                        .flat_map(|expr| {
-                            self.flatten_expr(
-                                expr,
-                                variants.clone(),
-                                variant_group.clone(),
-                                format!("{}{}", prev_prop, prop),
-                            )
-                        })
-                        .collect(),
-                })
-                .collect(),
+                    })
+                    .collect()
+            }
             Expr::RawCss(raw_css) => vec![CssEntry {
                 variants: None,
-                css: FlatUtilOrRaw::RawCss(raw_css.clone()),
+                css: Spanned::new(FlatUtilOrRaw::RawCss(raw_css.node.clone()), raw_css.span),
             }],
             Expr::Variant {
                 variants: v,
-                variant_group,
+                variant_group: own_group,
                 exprs,
             } => {
                 variants.append(&mut v.clone());
+                let variant_group = own_group.clone().or(variant_group);
                 exprs
                     .iter()
                     .flat_map(|e| {
@@ -243,10 +745,655 @@ impl CssWriter {
                             variants.clone(),
                             variant_group.clone(),
                             prev_prop.clone(),
+                            cx,
                         )
                     })
                     .collect()
             }
         }
     }
+
+    /// Splices in the parsed shortcut, as if its ecss was written where `name`
+    /// is used
+    fn flatten_shortcut(
+        &self,
+        name: &Spanned<String>,
+        variants: Vec<Spanned<Variant>>,
+        variant_group: Option<VariantGroup>,
+        cx: &mut FlattenContext,
+    ) -> Vec<CssEntry> {
+        if cx.expanding.contains(&name.node) {
+            cx.errors.push(CssError {
+                kind: CssErrorKind::RecursiveShortcut {
+                    name: name.node.clone(),
+                },
+                span: name.span,
+            });
+            return Vec::new();
+        }
+        let source = lookup(cx.shortcuts, &name.node).expect("Only shortcuts are expanded");
+
+        let (tokens, lex_errors) = lexer::get_tokens(source);
+        let (mut ast, parse_errors) = Parser::from(tokens).parse();
+        let first_error = lex_errors
+            .first()
+            .map(ToString::to_string)
+            .or_else(|| parse_errors.first().map(ToString::to_string));
+        if let Some(error) = first_error {
+            cx.errors.push(CssError {
+                kind: CssErrorKind::InvalidShortcut {
+                    name: name.node.clone(),
+                    error,
+                },
+                span: name.span,
+            });
+            return Vec::new();
+        }
+
+        // Errors and source maps point at the use of the shortcut
+        for expr in &mut ast {
+            expr.respan(name.span);
+        }
+        cx.expanding.push(name.node.clone());
+        let entries = ast
+            .iter()
+            .flat_map(|expr| {
+                self.flatten_expr(
+                    expr,
+                    variants.clone(),
+                    variant_group.clone(),
+                    String::new(),
+                    cx,
+                )
+            })
+            .collect();
+        cx.expanding.pop();
+        entries
+    }
+}
+
+/// Sorts so order won't change hash. Pseudo elements go last in the author's
+/// order, since `::before::marker` and `::marker::before` aren't the same and
+/// `::before:hover` isn't valid
+fn sort_variants(variants: &mut [Spanned<Variant>]) {
+    let is_pseudo_element = |variant: &Variant| match variant {
+        Variant::Pseudo(Value::Iden(name)) => is_pseudo_element(name),
+        Variant::Pseudo(Value::Raw(raw)) => raw.starts_with(':'),
+        Variant::Media(_) => false,
+    };
+    variants.sort_by(
+        |a, b| match (is_pseudo_element(&a.node), is_pseudo_element(&b.node)) {
+            (false, false) => a.node.cmp(&b.node),
+            (a, b) => a.cmp(&b),
+        },
+    );
+}
+
+/// Base rules first, then pseudo variants, then media queries from the smallest
+/// breakpoint up, so later rules override earlier ones like in mobile-first CSS.
+/// Media queries without a breakpoint go before the breakpoints
+fn cascade_order(a: &Class, b: &Class) -> Ordering {
+    let media = |class: &Class| !class.rule.media_queries.is_empty();
+    let breakpoint = |class: &Class| {
+        class
+            .rule
+            .media_queries
+            .iter()
+            .filter_map(|media_query| min_width(media_query))
+            .fold(0.0, f64::max)
+    };
+    let below = |class: &Class| {
+        class
+            .rule
+            .media_queries
+            .iter()
+            .filter_map(|media_query| max_width(media_query))
+            .reduce(f64::min)
+    };
+    // `@max-md`, without a min width
+    let below_only = |class: &Class| below(class).is_some() && breakpoint(class) == 0.0;
+    let pseudo = |class: &Class| !class.rule.pseudo_selectors.is_empty();
+
+    // Max widths go from wide to narrow so the narrowest wins, and ranges go
+    // after the breakpoint they start at
+    let by_max_width = |a: Option<f64>, b: Option<f64>| match (a, b) {
+        (Some(a), Some(b)) => b.total_cmp(&a),
+        (a, b) => a.is_some().cmp(&b.is_some()),
+    };
+
+    media(a)
+        .cmp(&media(b))
+        .then_with(|| below_only(b).cmp(&below_only(a)))
+        .then_with(|| breakpoint(a).total_cmp(&breakpoint(b)))
+        .then_with(|| by_max_width(below(a), below(b)))
+        .then_with(|| a.rule.media_queries.cmp(&b.rule.media_queries))
+        .then_with(|| pseudo(a).cmp(&pseudo(b)))
+        .then_with(|| a.index.cmp(&b.index))
+}
+
+/// Groups classes in cascade order that have the same rule. A class only joins
+/// an earlier group if no rule in between sets any of its properties, so moving
+/// it up doesn't change which rule wins
+fn merge_rules(classes: Vec<&Class>) -> Vec<Vec<&Class>> {
+    let mut merged: Vec<Vec<&Class>> = Vec::new();
+    'classes: for class in classes {
+        for group in merged.iter_mut().rev() {
+            let rule = &group[0].rule;
+            if rule.media_queries != class.rule.media_queries {
+                break;
+            }
+            if rule.pseudo_selectors == class.rule.pseudo_selectors
+                && rule.declarations == class.rule.declarations
+            {
+                group.push(class);
+                continue 'classes;
+            }
+            if declarations_overlap(&rule.declarations, &class.rule.declarations) {
+                break;
+            }
+        }
+        merged.push(vec![class]);
+    }
+
+    merged
+}
+
+/// Whether two declaration blocks might set the same property. Errs on the side
+/// of overlapping, shorthands like `padding` and `padding-left` overlap
+fn declarations_overlap(a: &str, b: &str) -> bool {
+    let properties_a: Vec<&str> = declared_properties(a).collect();
+    declared_properties(b).any(|b| properties_a.iter().any(|a| properties_overlap(a, b)))
+}
+
+fn declared_properties(declarations: &str) -> impl Iterator<Item = &str> {
+    split_declarations(declarations)
+        .filter_map(|declaration| declaration.split_once(':'))
+        .map(|(property, _)| property.trim())
+}
+
+/// Splits on the `;` that end declarations, not those in strings or
+/// parentheses like `url(data:a;b)`
+fn split_declarations(declarations: &str) -> impl Iterator<Item = &str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in declarations.char_indices() {
+        match quote {
+            _ if escaped => escaped = false,
+            _ if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' => quote = Some(c),
+                '(' | '[' => depth += 1,
+                ')' | ']' => depth = depth.saturating_sub(1),
+                ';' if depth == 0 => {
+                    parts.push(&declarations[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            },
+        }
+    }
+    parts.push(&declarations[start..]);
+    parts.into_iter()
+}
+
+fn properties_overlap(a: &str, b: &str) -> bool {
+    // Shorthands that don't share the first word with what they set
+    const RELATED: &[&[&str]] = &[
+        &["inset", "top", "right", "bottom", "left"],
+        &["font", "line"],
+        &["gap", "row", "column", "columns", "grid"],
+        &["place", "align", "justify"],
+    ];
+
+    if a == b || a == "all" || b == "all" {
+        return true;
+    }
+    if a.starts_with("--") || b.starts_with("--") {
+        return false;
+    }
+    // `-webkit-appearance` is `appearance`
+    let root = |property: &str| {
+        let property = match property.strip_prefix('-') {
+            Some(vendor_prefixed) => vendor_prefixed
+                .split_once('-')
+                .map_or(vendor_prefixed, |(_, property)| property),
+            None => property,
+        };
+        property.split('-').next().unwrap_or(property).to_string()
+    };
+    let (a, b) = (root(a), root(b));
+
+    a == b
+        || RELATED
+            .iter()
+            .any(|roots| roots.contains(&a.as_str()) && roots.contains(&b.as_str()))
+}
+
+fn check_value_group(
+    values: usize,
+    span: Span,
+    variant_group: Option<&VariantGroup>,
+) -> Result<(), CssError> {
+    let kind = match variant_group {
+        Some(group) if group.variants.len() == values => return Ok(()),
+        Some(group) => CssErrorKind::GroupLengthMismatch {
+            variants: group.variants.len(),
+            values,
+            variant_group: group.span,
+        },
+        None => CssErrorKind::MissingVariantGroup { values },
+    };
+
+    Err(CssError { kind, span })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        compiler::{ClassNaming, Compiler, CompilerOptions, OutputStyle},
+        error::CompileError,
+        manifest::{Manifest, ManifestClass},
+        theme::Theme,
+    };
+
+    fn css(code: &str) -> String {
+        let mut compiler = Compiler::new();
+        compiler.generate_classes(code).unwrap();
+        compiler.get_css()
+    }
+
+    fn css_errors(code: &str) -> Vec<CssError> {
+        Compiler::new()
+            .generate_classes(code)
+            .unwrap_err()
+            .into_iter()
+            .map(|err| match err {
+                CompileError::Css(err) => err,
+                err => panic!("Not a CSS error: {}", err),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn value_group_needs_as_many_values_as_variants() {
+        assert_eq!(
+            css_errors("(hover focus):p=(1 2 3)"),
+            [CssError {
+                kind: CssErrorKind::GroupLengthMismatch {
+                    variants: 2,
+                    values: 3,
+                    variant_group: Span::new(0, 13),
+                },
+                span: Span::new(16, 23),
+            }]
+        );
+    }
+
+    #[test]
+    fn value_group_needs_variant_group() {
+        assert_eq!(
+            css_errors("m=1 p=(1 2)"),
+            [CssError {
+                kind: CssErrorKind::MissingVariantGroup { values: 2 },
+                span: Span::new(6, 11),
+            }]
+        );
+    }
+
+    #[test]
+    fn nested_value_groups_use_the_outer_variant_group() {
+        assert_eq!(
+            css("(_ hover):(p=(1 2) border-(w=(2 4)))"),
+            "._E0{padding:0.25rem;}._E2{border-width:2px;}._E1:hover{padding:0.5rem;}._E3:hover{border-width:4px;}"
+        );
+        assert_eq!(
+            css_errors("(_ hover):(m=1 border-(w=(2 4 8)))"),
+            [CssError {
+                kind: CssErrorKind::GroupLengthMismatch {
+                    variants: 2,
+                    values: 3,
+                    variant_group: Span::new(0, 9),
+                },
+                span: Span::new(25, 32),
+            }]
+        );
+    }
+
+    #[test]
+    fn cascade_order() {
+        // Base, pseudo classes, other media queries, then breakpoints from
+        // the smallest up, whatever order they're written in
+        assert_eq!(
+            css("@lg:p=8 @md:p=4 hover:p=3 p=0 @dark:p=5 @md:hover:p=6 @[(min-width: 40em)]:m=1"),
+            concat!(
+                "._E3{padding:0px;}",
+                "._E2:hover{padding:0.75rem;}",
+                "@media (prefers-color-scheme: dark){._E4{padding:1.25rem;}}",
+                "@media (min-width: 40em){._E6{margin:0.25rem;}}",
+                "@media (min-width:768px){._E1{padding:1rem;}._E5:hover{padding:1.5rem;}}",
+                "@media (min-width:1024px){._E0{padding:2rem;}}",
+            )
+        );
+    }
+
+    #[test]
+    fn max_and_range_breakpoints() {
+        // Max widths from wide to narrow, ranges after the breakpoint they
+        // start at
+        assert_eq!(
+            css("@md-lg:p=6 @max-lg:p=3 p=4 @max-md:p=2 @md:p=5"),
+            concat!(
+                "._E2{padding:1rem;}",
+                "@media (max-width:1023.98px){._E1{padding:0.75rem;}}",
+                "@media (max-width:767.98px){._E3{padding:0.5rem;}}",
+                "@media (min-width:768px){._E4{padding:1.25rem;}}",
+                "@media (min-width:768px) and (max-width:1023.98px){._E0{padding:1.5rem;}}",
+            )
+        );
+    }
+
+    #[test]
+    fn pseudo_elements_go_last() {
+        assert_eq!(
+            css("before:hover:p=1"),
+            "._E0:hover::before{padding:0.25rem;}"
+        );
+    }
+
+    #[test]
+    fn pseudo_elements_keep_the_authors_order() {
+        assert_eq!(
+            css("before:marker:focus:p=1 marker:before:p=1 [:first-line]:hover:after:m=1"),
+            concat!(
+                "._E0:focus::before::marker{padding:0.25rem;}",
+                "._E1::marker::before{padding:0.25rem;}",
+                "._E2:hover::first-line::after{margin:0.25rem;}",
+            )
+        );
+    }
+
+    #[test]
+    fn merges_equal_rules() {
+        assert_eq!(
+            css("p=1 m=2 [padding:0.25rem] hover:p=1"),
+            "._E0,._E2{padding:0.25rem;}._E1{margin:0.5rem;}._E3:hover{padding:0.25rem;}"
+        );
+        assert_eq!(
+            css("@md:p=1 @md:m=2 @md:p=[0.25rem] @lg:p=1"),
+            concat!(
+                "@media (min-width:768px){._E0,._E2{padding:0.25rem;}._E1{margin:0.5rem;}}",
+                "@media (min-width:1024px){._E3{padding:0.25rem;}}",
+            )
+        );
+    }
+
+    #[test]
+    fn merging_never_changes_the_cascade() {
+        // `._E1` sets `padding` between the two equal rules
+        assert_eq!(
+            css("p=1 [padding:0px] [padding:0.25rem]"),
+            "._E0{padding:0.25rem;}._E1{padding:0px;}._E2{padding:0.25rem;}"
+        );
+        // `padding` also sets `padding-left`
+        assert_eq!(
+            css("p=1 [padding-left:0px] [padding:0.25rem]"),
+            "._E0{padding:0.25rem;}._E1{padding-left:0px;}._E2{padding:0.25rem;}"
+        );
+    }
+
+    fn css_with_comments(code: &str, output_style: OutputStyle) -> String {
+        let mut compiler = Compiler::with_options(CompilerOptions {
+            source_comments: true,
+            output_style,
+            ..Default::default()
+        });
+        compiler.generate_classes(code).unwrap();
+        compiler.get_css()
+    }
+
+    #[test]
+    fn source_comments() {
+        let code = "p=1 hover:m=2 @md:p=1 [padding:0.25rem] [content:'*/']";
+        assert_eq!(
+            css_with_comments(code, OutputStyle::Minified),
+            concat!(
+                "/* p=1, [padding:0.25rem] */._E0,._E3{padding:0.25rem;}",
+                "/* [content:'* /'] */._E4{content:'*/';}",
+                "/* hover:m=2 */._E1:hover{margin:0.5rem;}",
+                "@media (min-width:768px){/* @md:p=1 */._E2{padding:0.25rem;}}",
+            )
+        );
+        assert_eq!(
+            css_with_comments(code, OutputStyle::Pretty),
+            concat!(
+                "/* p=1, [padding:0.25rem] */\n",
+                "._E0,\n",
+                "._E3 {\n",
+                "  padding: 0.25rem;\n",
+                "}\n",
+                "\n",
+                "/* [content:'* /'] */\n",
+                "._E4 {\n",
+                "  content: '*/';\n",
+                "}\n",
+                "\n",
+                "/* hover:m=2 */\n",
+                "._E1:hover {\n",
+                "  margin: 0.5rem;\n",
+                "}\n",
+                "\n",
+                "@media (min-width:768px) {\n",
+                "  /* @md:p=1 */\n",
+                "  ._E2 {\n",
+                "    padding: 0.25rem;\n",
+                "  }\n",
+                "}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn describes_classes() {
+        let mut compiler = Compiler::new();
+        compiler
+            .generate_classes("p=1 hover:@md:bg-c=blue-500 [color:red]")
+            .unwrap();
+        assert_eq!(
+            compiler.describe_class("_E1"),
+            Some(ClassDescription {
+                name: String::from("_E1"),
+                variants: vec![String::from("@md"), String::from("hover")],
+                property: Some(String::from("bg-c")),
+                value: Some(String::from("blue-500")),
+                css: String::from(
+                    "@media (min-width:768px){._E1:hover{background-color:#3b82f6;}}"
+                ),
+                source: String::from("@md:hover:bg-c=blue-500"),
+            })
+        );
+        assert_eq!(
+            compiler.describe_class("_E2"),
+            Some(ClassDescription {
+                name: String::from("_E2"),
+                variants: Vec::new(),
+                property: None,
+                value: Some(String::from("color:red")),
+                css: String::from("._E2{color:red;}"),
+                source: String::from("[color:red]"),
+            })
+        );
+        assert_eq!(compiler.describe_class("_E3"), None);
+        assert_eq!(compiler.describe_class("p=1"), None);
+    }
+
+    #[test]
+    fn describes_classes_of_every_naming() {
+        let namings = [
+            (ClassNaming::default(), "_E0"),
+            (
+                ClassNaming::Base62 {
+                    prefix: String::from("_"),
+                },
+                "_0",
+            ),
+            (
+                ClassNaming::ContentHash {
+                    prefix: String::from("_"),
+                },
+                "_XnZDoyln",
+            ),
+            (ClassNaming::Readable, "p_1"),
+        ];
+        for (class_naming, name) in namings {
+            let mut compiler = Compiler::with_options(CompilerOptions {
+                class_naming,
+                ..Default::default()
+            });
+            assert_eq!(compiler.generate_classes("p=1").unwrap(), [name]);
+            let description = compiler.describe_class(name).unwrap();
+            assert_eq!(description.source, "p=1");
+            assert_eq!(description.css, format!(".{}{{padding:0.25rem;}}", name));
+            assert_eq!(compiler.describe_class("_E9"), None);
+        }
+    }
+
+    fn with_shortcuts(shortcuts: &str) -> Compiler {
+        Compiler::with_options(CompilerOptions {
+            theme: Theme::from_toml(&format!("[shortcuts]\n{}", shortcuts)).unwrap(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn shortcuts_take_use_site_variants() {
+        let mut compiler = with_shortcuts(r#"btn = "p=2 focus:m=1""#);
+        compiler.generate_classes("hover:btn").unwrap();
+        assert_eq!(
+            compiler.get_css(),
+            "._E0:hover{padding:0.5rem;}._E1:focus:hover{margin:0.25rem;}"
+        );
+    }
+
+    #[test]
+    fn shortcuts_can_use_shortcuts() {
+        let mut compiler = with_shortcuts(
+            r#"
+            btn = "p=2 m=1"
+            primary = "btn bg-c=blue-500"
+            "#,
+        );
+        compiler.generate_classes("primary").unwrap();
+        assert_eq!(
+            compiler.get_css(),
+            "._E0{padding:0.5rem;}._E1{margin:0.25rem;}._E2{background-color:#3b82f6;}"
+        );
+    }
+
+    #[test]
+    fn recursive_shortcuts_are_errors() {
+        let mut compiler = with_shortcuts(
+            r#"
+            a = "p=1 b"
+            b = "m=1 a"
+            "#,
+        );
+        assert_eq!(
+            compiler.generate_classes("hover:a").unwrap_err(),
+            [CompileError::Css(CssError {
+                kind: CssErrorKind::RecursiveShortcut {
+                    name: String::from("a"),
+                },
+                span: Span { start: 6, end: 7 },
+            })]
+        );
+    }
+
+    #[test]
+    fn invalid_shortcuts_are_errors() {
+        let mut compiler = with_shortcuts(r#"btn = "p=1 (m=2""#);
+        assert_eq!(
+            compiler.generate_classes("m=1 btn").unwrap_err(),
+            [CompileError::Css(CssError {
+                kind: CssErrorKind::InvalidShortcut {
+                    name: String::from("btn"),
+                    error: String::from("Expected word or `)`, found `=`"),
+                },
+                span: Span { start: 4, end: 7 },
+            })]
+        );
+    }
+
+    fn manifest(classes: &[(u32, &str, &str)]) -> Manifest {
+        Manifest {
+            class_count: classes.len() as u32,
+            classes: classes
+                .iter()
+                .map(|(index, name, source)| ManifestClass {
+                    index: *index,
+                    name: name.to_string(),
+                    source: source.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn pretty_keeps_semicolons_in_values() {
+        let mut compiler = Compiler::with_options(CompilerOptions {
+            output_style: OutputStyle::Pretty,
+            ..Default::default()
+        });
+        compiler
+            .generate_classes("[background:url('a;b')] [background:url(data:a;b)] p=1")
+            .unwrap();
+        assert_eq!(
+            compiler.get_css(),
+            "._E0 {\n  background: url('a;b');\n}\n\n._E1 {\n  background: url(data:a;b);\n}\n\n._E2 {\n  padding: 0.25rem;\n}\n"
+        );
+    }
+
+    #[test]
+    fn split_declarations_outside_strings_and_parens() {
+        let declarations = r#"content:"a;b";background:url(x;y);color:red;"#;
+        assert_eq!(
+            split_declarations(declarations).collect::<Vec<_>>(),
+            [r#"content:"a;b""#, "background:url(x;y)", "color:red", ""]
+        );
+        assert_eq!(
+            declared_properties(declarations).collect::<Vec<_>>(),
+            ["content", "background", "color"]
+        );
+    }
+
+    #[test]
+    fn manifest_keeps_names() {
+        let mut compiler = Compiler::new();
+        compiler.load_manifest(manifest(&[(0, "_E0", "m=2"), (1, "_E1", "p=1")]));
+        assert_eq!(
+            compiler.generate_classes("p=1 m=4").unwrap(),
+            ["_E1", "_E2"]
+        );
+        assert_eq!(compiler.generate_classes("m=2").unwrap(), ["_E0"]);
+    }
+
+    #[test]
+    fn manifest_loaded_late_never_reuses_names() {
+        let mut compiler = Compiler::new();
+        assert_eq!(compiler.generate_classes("p=1").unwrap(), ["_E0"]);
+        compiler.load_manifest(manifest(&[(0, "_E0", "m=2"), (1, "_E1", "p=1")]));
+        assert_eq!(
+            compiler.generate_classes("m=2 p=1").unwrap(),
+            ["_E2", "_E0"]
+        );
+        assert_eq!(
+            compiler.get_css(),
+            "._E0{padding:0.25rem;}._E2{margin:0.5rem;}"
+        );
+    }
 }
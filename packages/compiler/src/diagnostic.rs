@@ -0,0 +1,465 @@
+use crate::{
+    css_writer::CssErrorKind,
+    error::{CompileError, ErrorCode},
+    lexer::LexErrorKind,
+    parser::ParseErrorKind,
+    span::Span,
+    utils::UtilError,
+};
+
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Level {
+    Error,
+    Warning,
+}
+
+/// An error prepared for humans, rendered like rustc diagnostics:
+///
+/// ```text
+/// error[E0101]: Unknown pseudo class: hovr
+///  --> 1:1
+///   |
+/// 1 | hovr:p=1
+///   | ^^^^ unknown pseudo class
+///   |
+///   = help: did you mean `hover`?
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct Diagnostic {
+    pub level: Level,
+    pub code: ErrorCode,
+    pub message: String,
+    pub span: Span,
+
+    /// Shown next to the underlined source
+    pub label: Option<String>,
+
+    /// Other related source, underlined with `-`
+    pub secondary_labels: Vec<(Span, String)>,
+
+    pub help: Option<String>,
+}
+
+/// A span resolved to its line, ready to be underlined
+struct Annotation<'a> {
+    line_nr: usize,
+    line: &'a str,
+    column: usize,
+    len: usize,
+    marker: &'static str,
+    color: &'static str,
+    label: Option<&'a str>,
+}
+
+impl Diagnostic {
+    /// The diagnostic of an error that doesn't stop compiling, like the ones
+    /// kept by `ErrorPolicy::Warn`
+    pub fn warning(err: &CompileError) -> Self {
+        Self {
+            level: Level::Warning,
+            ..Self::from(err)
+        }
+    }
+
+    pub fn render(&self, source: &str) -> String {
+        self.render_with(source, false)
+    }
+
+    /// Rendered with ANSI colors, for terminals
+    pub fn render_ansi(&self, source: &str) -> String {
+        self.render_with(source, true)
+    }
+
+    fn render_with(&self, source: &str, ansi: bool) -> String {
+        let paint = |color: &'static str| if ansi { color } else { "" };
+        let (level, level_color) = match self.level {
+            Level::Error => ("error", paint(RED)),
+            Level::Warning => ("warning", paint(YELLOW)),
+        };
+        let (blue, bold, reset) = (paint(BLUE), paint(BOLD), paint(RESET));
+
+        let primary = annotate(source, self.span, "^", level_color, self.label.as_deref());
+        let mut annotations: Vec<Annotation> = self
+            .secondary_labels
+            .iter()
+            .map(|(span, label)| annotate(source, *span, "-", blue, Some(label)))
+            .collect();
+        let (line_nr, column) = (primary.line_nr, primary.column);
+        annotations.push(primary);
+        annotations.sort_by_key(|annotation| (annotation.line_nr, annotation.column));
+
+        let gutter = " ".repeat(
+            annotations
+                .iter()
+                .map(|annotation| annotation.line_nr.to_string().len())
+                .max()
+                .unwrap_or(1),
+        );
+
+        let mut out = format!(
+            "{}{}[{}]{}{}: {}{}\n",
+            level_color, level, self.code, reset, bold, self.message, reset
+        );
+        out.push_str(&format!(
+            "{}{}-->{} {}:{}\n",
+            gutter,
+            blue,
+            reset,
+            line_nr,
+            column + 1
+        ));
+        out.push_str(&format!("{} {}|{}\n", gutter, blue, reset));
+        let mut prev_line_nr = None;
+        for annotation in &annotations {
+            if prev_line_nr != Some(annotation.line_nr) {
+                out.push_str(&format!(
+                    "{}{:<width$} |{} {}\n",
+                    blue,
+                    annotation.line_nr,
+                    reset,
+                    annotation.line,
+                    width = gutter.len()
+                ));
+                prev_line_nr = Some(annotation.line_nr);
+            }
+            out.push_str(&format!(
+                "{} {}|{} {}{}{}",
+                gutter,
+                blue,
+                reset,
+                " ".repeat(annotation.column),
+                annotation.color,
+                annotation.marker.repeat(annotation.len)
+            ));
+            if let Some(label) = annotation.label {
+                out.push_str(&format!(" {}", label));
+            }
+            out.push_str(&format!("{}\n", reset));
+        }
+        if let Some(help) = &self.help {
+            out.push_str(&format!("{} {}|{}\n", gutter, blue, reset));
+            out.push_str(&format!(
+                "{} {}={} {}help{}: {}\n",
+                gutter, blue, reset, bold, reset, help
+            ));
+        }
+
+        out
+    }
+}
+
+fn annotate<'a>(
+    source: &'a str,
+    span: Span,
+    marker: &'static str,
+    color: &'static str,
+    label: Option<&'a str>,
+) -> Annotation<'a> {
+    // The span may come from other code, keep it inside `source`
+    let start = char_boundary(source, span.start);
+    let end = char_boundary(source, span.end).max(start);
+    let (line_nr, line, line_offset) = find_line(source, start);
+    let line_end = start - line_offset + line.len();
+    let len = source[start..end.min(line_end)].chars().count().max(1);
+
+    Annotation {
+        line_nr,
+        line,
+        column: line[..line_offset].chars().count(),
+        len,
+        marker,
+        color,
+        label,
+    }
+}
+
+/// `offset` moved back to the nearest char boundary of `source`
+fn char_boundary(source: &str, offset: usize) -> usize {
+    let mut offset = offset.min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+/// Line number (from 1), the line, and the byte offset of `offset` within it
+fn find_line(source: &str, offset: usize) -> (usize, &str, usize) {
+    let offset = offset.min(source.len());
+    let line_start = source[..offset].rfind('\n').map_or(0, |index| index + 1);
+    let line_end = source[offset..]
+        .find('\n')
+        .map_or(source.len(), |index| offset + index);
+    let line_nr = source[..line_start].matches('\n').count() + 1;
+
+    (line_nr, &source[line_start..line_end], offset - line_start)
+}
+
+fn did_you_mean(suggestions: &[String]) -> Option<String> {
+    let suggestions: Vec<String> = suggestions
+        .iter()
+        .map(|suggestion| format!("`{}`", suggestion))
+        .collect();
+    match suggestions.split_last() {
+        None => None,
+        Some((last, [])) => Some(format!("did you mean {}?", last)),
+        Some((last, rest)) => Some(format!("did you mean {} or {}?", rest.join(", "), last)),
+    }
+}
+
+impl From<&CompileError> for Diagnostic {
+    fn from(err: &CompileError) -> Self {
+        let code = err.code();
+        match err {
+            CompileError::Lex(err) => {
+                let span = Span::new(err.offset, err.offset + err.char.len_utf8());
+                match err.kind {
+                    LexErrorKind::UnterminatedRawValue => Diagnostic {
+                        level: Level::Error,
+                        code,
+                        message: String::from("Unterminated raw value"),
+                        span,
+                        label: Some(String::from("this `[` is never closed")),
+                        secondary_labels: Vec::new(),
+                        help: Some(String::from("add a `]` after the raw value")),
+                    },
+                    LexErrorKind::UnknownChar => Diagnostic {
+                        level: Level::Error,
+                        code,
+                        message: format!("Unknown char: {}", err.char),
+                        span,
+                        label: Some(String::from("unknown char")),
+                        secondary_labels: Vec::new(),
+                        help: Some(String::from(
+                            "names and values can only contain letters, digits, `-`, `.` and `/`, use `[...]` for anything else",
+                        )),
+                    },
+                }
+            }
+            CompileError::Parse(err) => match err.kind {
+                ParseErrorKind::UnexpectedToken => Diagnostic {
+                    level: Level::Error,
+                    code,
+                    message: err.to_string(),
+                    span: err.span,
+                    label: match err.expected.split_last() {
+                        None => Some(String::from("unexpected token")),
+                        Some((last, [])) => Some(format!("expected {}", last)),
+                        Some((last, rest)) => {
+                            let rest: Vec<String> =
+                                rest.iter().map(|kind| kind.to_string()).collect();
+                            Some(format!("expected {} or {}", rest.join(", "), last))
+                        }
+                    },
+                    secondary_labels: Vec::new(),
+                    help: None,
+                },
+                ParseErrorKind::MultipleVariantGroups => Diagnostic {
+                    level: Level::Error,
+                    code,
+                    message: err.to_string(),
+                    span: err.span,
+                    label: Some(String::from("second variant group")),
+                    secondary_labels: Vec::new(),
+                    help: Some(String::from(
+                        "nest the expression to use another variant group: `(_ @md):((hover focus):p=(1 2))`",
+                    )),
+                },
+                ParseErrorKind::EmptyValueGroup => Diagnostic {
+                    level: Level::Error,
+                    code,
+                    message: err.to_string(),
+                    span: err.span,
+                    label: Some(String::from("empty value group")),
+                    secondary_labels: Vec::new(),
+                    help: Some(String::from("use `_` to skip a variant: `p=(1 _ 3)`")),
+                },
+            },
+            CompileError::Css(err) => match &err.kind {
+                CssErrorKind::Util(util_err) => {
+                    let (label, fallback_help) = match util_err {
+                        UtilError::UnknownUtil { .. } => ("unknown util", None),
+                        UtilError::UnexpectedValue { property, .. } => (
+                            "doesn't take a value",
+                            Some(format!("remove the value: `{}`", property)),
+                        ),
+                        UtilError::MissingValue { property } => (
+                            "needs a value",
+                            Some(format!("assign a value: `{}=...`", property)),
+                        ),
+                        UtilError::InvalidValue { property, .. } => (
+                            "invalid value",
+                            Some(format!(
+                                "use `[...]` for arbitrary values: `{}=[...]`",
+                                property
+                            )),
+                        ),
+                        UtilError::Plugin { .. } => ("rejected by plugin", None),
+                        UtilError::UnknownPseudo { .. } => (
+                            "unknown pseudo class",
+                            Some(String::from(
+                                "use `[...]` for other pseudo classes: `[nth-child(3)]:`",
+                            )),
+                        ),
+                        UtilError::UnknownMediaQuery { .. } => (
+                            "unknown media query",
+                            Some(String::from(
+                                "use `@[...]` for other media queries: `@[(min-width: 1234px)]:`",
+                            )),
+                        ),
+                        UtilError::EmptyBreakpointRange { from, to, .. } => (
+                            "empty breakpoint range",
+                            Some(format!("put the smaller breakpoint first: `@{}-{}`", to, from)),
+                        ),
+                    };
+                    Diagnostic {
+                        level: Level::Error,
+                        code,
+                        message: util_err.message(),
+                        span: err.span,
+                        label: Some(String::from(label)),
+                        secondary_labels: Vec::new(),
+                        help: did_you_mean(util_err.suggestions()).or(fallback_help),
+                    }
+                }
+                CssErrorKind::GroupLengthMismatch { variant_group, .. } => Diagnostic {
+                    level: Level::Error,
+                    code,
+                    message: err.to_string(),
+                    span: err.span,
+                    label: Some(String::from("value group")),
+                    secondary_labels: vec![(*variant_group, String::from("variant group"))],
+                    help: Some(String::from(
+                        "give one value per variant, use `_` to skip a variant: `(_ @md @lg):p=(1 _ 3)`",
+                    )),
+                },
+                CssErrorKind::MissingVariantGroup { .. } => Diagnostic {
+                    level: Level::Error,
+                    code,
+                    message: err.to_string(),
+                    span: err.span,
+                    label: Some(String::from("value group")),
+                    secondary_labels: Vec::new(),
+                    help: Some(String::from(
+                        "add a variant group to match the values against: `(_ @md):p=(1 2)`",
+                    )),
+                },
+                CssErrorKind::InvalidShortcut { .. } => Diagnostic {
+                    level: Level::Error,
+                    code,
+                    message: err.to_string(),
+                    span: err.span,
+                    label: Some(String::from("shortcut")),
+                    secondary_labels: Vec::new(),
+                    help: Some(String::from("fix the shortcut in the theme")),
+                },
+                CssErrorKind::RecursiveShortcut { .. } => Diagnostic {
+                    level: Level::Error,
+                    code,
+                    message: err.to_string(),
+                    span: err.span,
+                    label: Some(String::from("used by itself")),
+                    secondary_labels: Vec::new(),
+                    help: None,
+                },
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerOptions, ErrorPolicy};
+
+    fn diagnostics(code: &str) -> Vec<Diagnostic> {
+        Compiler::new()
+            .generate_classes(code)
+            .unwrap_err()
+            .iter()
+            .map(Diagnostic::from)
+            .collect()
+    }
+
+    #[test]
+    fn renders_span() {
+        assert_eq!(
+            diagnostics("m=2\np=banana")[0].render("m=2\np=banana"),
+            concat!(
+                "error[E0302]: Invalid value banana for p, tried get_spacing_value\n",
+                " --> 2:3\n",
+                "  |\n",
+                "2 | p=banana\n",
+                "  |   ^^^^^^ invalid value\n",
+                "  |\n",
+                "  = help: use `[...]` for arbitrary values: `p=[...]`\n",
+            )
+        );
+    }
+
+    #[test]
+    fn unterminated_raw_value_is_one_error() {
+        let diagnostics = diagnostics("m=2 p=[1 hover:m=1");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].render("m=2 p=[1 hover:m=1"),
+            concat!(
+                "error[E0001]: Unterminated raw value\n",
+                " --> 1:7\n",
+                "  |\n",
+                "1 | m=2 p=[1 hover:m=1\n",
+                "  |       ^ this `[` is never closed\n",
+                "  |\n",
+                "  = help: add a `]` after the raw value\n",
+            )
+        );
+    }
+
+    #[test]
+    fn renders_warning() {
+        let mut compiler = Compiler::with_options(CompilerOptions {
+            error_policy: ErrorPolicy::Warn,
+            ..Default::default()
+        });
+        assert!(compiler.generate_classes("hovr:p=1").is_ok());
+        let warnings = compiler.take_warnings();
+        let diagnostic = Diagnostic::warning(&warnings[0]);
+        assert_eq!(diagnostic.level, Level::Warning);
+        assert_eq!(
+            diagnostic.render("hovr:p=1"),
+            concat!(
+                "warning[E0101]: Unknown pseudo class: hovr\n",
+                " --> 1:1\n",
+                "  |\n",
+                "1 | hovr:p=1\n",
+                "  | ^^^^ unknown pseudo class\n",
+                "  |\n",
+                "  = help: did you mean `hover`?\n",
+            )
+        );
+    }
+
+    #[test]
+    fn clamps_span_to_source() {
+        let diagnostic = &diagnostics("m=2 p=banana")[0];
+        assert_eq!(
+            diagnostic.render("p"),
+            concat!(
+                "error[E0302]: Invalid value banana for p, tried get_spacing_value\n",
+                " --> 1:2\n",
+                "  |\n",
+                "1 | p\n",
+                "  |  ^ invalid value\n",
+                "  |\n",
+                "  = help: use `[...]` for arbitrary values: `p=[...]`\n",
+            )
+        );
+        diagnostic.render("ü");
+        diagnostic.render("");
+    }
+}
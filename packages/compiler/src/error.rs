@@ -0,0 +1,80 @@
+use std::fmt;
+
+pub use crate::css_writer::{CssError, CssErrorKind};
+pub use crate::error_code::ErrorCode;
+pub use crate::lexer::{LexError, LexErrorKind};
+pub use crate::parser::{ParseError, ParseErrorKind};
+pub use crate::span::Span;
+pub use crate::token::{Token, TokenKind};
+pub use crate::utils::UtilError;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum CompileError {
+    Lex(LexError),
+    Parse(ParseError),
+    Css(CssError),
+}
+
+impl CompileError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            CompileError::Lex(err) => match err.kind {
+                LexErrorKind::UnterminatedRawValue => ErrorCode::UnterminatedRawValue,
+                LexErrorKind::UnknownChar => ErrorCode::UnknownChar,
+            },
+            CompileError::Parse(err) => match err.kind {
+                ParseErrorKind::UnexpectedToken => ErrorCode::UnexpectedToken,
+                ParseErrorKind::MultipleVariantGroups => ErrorCode::MultipleVariantGroups,
+                ParseErrorKind::EmptyValueGroup => ErrorCode::EmptyValueGroup,
+            },
+            CompileError::Css(err) => match &err.kind {
+                CssErrorKind::Util(UtilError::UnknownUtil { .. }) => ErrorCode::UnknownUtil,
+                CssErrorKind::Util(UtilError::UnexpectedValue { .. }) => ErrorCode::UnexpectedValue,
+                CssErrorKind::Util(UtilError::MissingValue { .. }) => ErrorCode::MissingValue,
+                CssErrorKind::Util(UtilError::InvalidValue { .. }) => ErrorCode::InvalidValue,
+                CssErrorKind::Util(UtilError::Plugin { .. }) => ErrorCode::RejectedByPlugin,
+                CssErrorKind::Util(UtilError::UnknownPseudo { .. }) => ErrorCode::UnknownPseudo,
+                CssErrorKind::Util(UtilError::UnknownMediaQuery { .. }) => {
+                    ErrorCode::UnknownMediaQuery
+                }
+                CssErrorKind::Util(UtilError::EmptyBreakpointRange { .. }) => {
+                    ErrorCode::EmptyBreakpointRange
+                }
+                CssErrorKind::GroupLengthMismatch { .. } => ErrorCode::GroupLengthMismatch,
+                CssErrorKind::MissingVariantGroup { .. } => ErrorCode::MissingVariantGroup,
+                CssErrorKind::InvalidShortcut { .. } => ErrorCode::InvalidShortcut,
+                CssErrorKind::RecursiveShortcut { .. } => ErrorCode::RecursiveShortcut,
+            },
+        }
+    }
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompileError::Lex(err) => err.fmt(f),
+            CompileError::Parse(err) => err.fmt(f),
+            CompileError::Css(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for CompileError {}
+
+impl From<LexError> for CompileError {
+    fn from(err: LexError) -> Self {
+        CompileError::Lex(err)
+    }
+}
+
+impl From<ParseError> for CompileError {
+    fn from(err: ParseError) -> Self {
+        CompileError::Parse(err)
+    }
+}
+
+impl From<CssError> for CompileError {
+    fn from(err: CssError) -> Self {
+        CompileError::Css(err)
+    }
+}
@@ -0,0 +1,495 @@
+use std::fmt;
+
+/// Stable identifier of an error, rendered as `E0101`. Codes are never reused,
+/// so tooling can match on them instead of on messages.
+///
+/// | Range | Errors               |
+/// | ----- | -------------------- |
+/// | E00xx | lexer                |
+/// | E01xx | variants             |
+/// | E02xx | parser               |
+/// | E03xx | utils                |
+/// | E04xx | variant/value groups |
+/// | E05xx | shortcuts            |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    UnterminatedRawValue,
+    UnknownChar,
+    UnknownPseudo,
+    UnknownMediaQuery,
+    EmptyBreakpointRange,
+    UnexpectedToken,
+    MultipleVariantGroups,
+    EmptyValueGroup,
+    UnknownUtil,
+    InvalidValue,
+    UnexpectedValue,
+    MissingValue,
+    RejectedByPlugin,
+    GroupLengthMismatch,
+    MissingVariantGroup,
+    InvalidShortcut,
+    RecursiveShortcut,
+}
+
+impl ErrorCode {
+    pub const ALL: &'static [ErrorCode] = &[
+        ErrorCode::UnterminatedRawValue,
+        ErrorCode::UnknownChar,
+        ErrorCode::UnknownPseudo,
+        ErrorCode::UnknownMediaQuery,
+        ErrorCode::EmptyBreakpointRange,
+        ErrorCode::UnexpectedToken,
+        ErrorCode::MultipleVariantGroups,
+        ErrorCode::EmptyValueGroup,
+        ErrorCode::UnknownUtil,
+        ErrorCode::InvalidValue,
+        ErrorCode::UnexpectedValue,
+        ErrorCode::MissingValue,
+        ErrorCode::RejectedByPlugin,
+        ErrorCode::GroupLengthMismatch,
+        ErrorCode::MissingVariantGroup,
+        ErrorCode::InvalidShortcut,
+        ErrorCode::RecursiveShortcut,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::UnterminatedRawValue => "E0001",
+            ErrorCode::UnknownChar => "E0002",
+            ErrorCode::UnknownPseudo => "E0101",
+            ErrorCode::UnknownMediaQuery => "E0102",
+            ErrorCode::EmptyBreakpointRange => "E0103",
+            ErrorCode::UnexpectedToken => "E0201",
+            ErrorCode::MultipleVariantGroups => "E0202",
+            ErrorCode::EmptyValueGroup => "E0203",
+            ErrorCode::UnknownUtil => "E0301",
+            ErrorCode::InvalidValue => "E0302",
+            ErrorCode::UnexpectedValue => "E0303",
+            ErrorCode::MissingValue => "E0304",
+            ErrorCode::RejectedByPlugin => "E0305",
+            ErrorCode::GroupLengthMismatch => "E0401",
+            ErrorCode::MissingVariantGroup => "E0402",
+            ErrorCode::InvalidShortcut => "E0501",
+            ErrorCode::RecursiveShortcut => "E0502",
+        }
+    }
+
+    /// `"E0101"` to `ErrorCode::UnknownPseudo`
+    pub fn from_code(code: &str) -> Option<Self> {
+        ErrorCode::ALL
+            .iter()
+            .copied()
+            .find(|error_code| error_code.as_str() == code)
+    }
+
+    /// Short description, `unknown pseudo class`
+    pub fn title(self) -> &'static str {
+        match self {
+            ErrorCode::UnterminatedRawValue => "unterminated raw value",
+            ErrorCode::UnknownChar => "unknown char",
+            ErrorCode::UnknownPseudo => "unknown pseudo class",
+            ErrorCode::UnknownMediaQuery => "unknown media query",
+            ErrorCode::EmptyBreakpointRange => "empty breakpoint range",
+            ErrorCode::UnexpectedToken => "unexpected token",
+            ErrorCode::MultipleVariantGroups => "multiple variant groups",
+            ErrorCode::EmptyValueGroup => "empty value group",
+            ErrorCode::UnknownUtil => "unknown util",
+            ErrorCode::InvalidValue => "invalid value",
+            ErrorCode::UnexpectedValue => "util doesn't take a value",
+            ErrorCode::MissingValue => "util needs a value",
+            ErrorCode::RejectedByPlugin => "rejected by plugin",
+            ErrorCode::GroupLengthMismatch => "value group doesn't match variant group",
+            ErrorCode::MissingVariantGroup => "value group without variant group",
+            ErrorCode::InvalidShortcut => "invalid shortcut",
+            ErrorCode::RecursiveShortcut => "recursive shortcut",
+        }
+    }
+
+    /// Long explanation with examples
+    pub fn explain(self) -> &'static str {
+        match self {
+            ErrorCode::UnterminatedRawValue => UNTERMINATED_RAW_VALUE,
+            ErrorCode::UnknownChar => UNKNOWN_CHAR,
+            ErrorCode::UnknownPseudo => UNKNOWN_PSEUDO,
+            ErrorCode::UnknownMediaQuery => UNKNOWN_MEDIA_QUERY,
+            ErrorCode::EmptyBreakpointRange => EMPTY_BREAKPOINT_RANGE,
+            ErrorCode::UnexpectedToken => UNEXPECTED_TOKEN,
+            ErrorCode::MultipleVariantGroups => MULTIPLE_VARIANT_GROUPS,
+            ErrorCode::EmptyValueGroup => EMPTY_VALUE_GROUP,
+            ErrorCode::UnknownUtil => UNKNOWN_UTIL,
+            ErrorCode::InvalidValue => INVALID_VALUE,
+            ErrorCode::UnexpectedValue => UNEXPECTED_VALUE,
+            ErrorCode::MissingValue => MISSING_VALUE,
+            ErrorCode::RejectedByPlugin => REJECTED_BY_PLUGIN,
+            ErrorCode::GroupLengthMismatch => GROUP_LENGTH_MISMATCH,
+            ErrorCode::MissingVariantGroup => MISSING_VARIANT_GROUP,
+            ErrorCode::InvalidShortcut => INVALID_SHORTCUT,
+            ErrorCode::RecursiveShortcut => RECURSIVE_SHORTCUT,
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+const UNTERMINATED_RAW_VALUE: &str = "\
+A raw value was opened with `[` but never closed with `]`.
+
+Raw values pass anything between the brackets straight to the CSS.
+
+```
+bg-c=[#111
+```
+
+Close it with `]`.
+
+```
+bg-c=[#111]
+```
+";
+
+const UNKNOWN_CHAR: &str = "\
+A char was found that can't start any token.
+
+Names and values can only contain letters, digits, `-`, `.` and `/`.
+
+```
+bg-c=#111
+```
+
+Use a raw value for anything else.
+
+```
+bg-c=[#111]
+```
+";
+
+const UNKNOWN_PSEUDO: &str = "\
+A pseudo class variant was used that doesn't exist.
+
+```
+hovr:border-c=neutral-200
+```
+
+See the variants page for the pseudo classes and pseudo elements that can be
+used, for example `hover`, `focus`, `first` or `before`.
+
+```
+hover:border-c=neutral-200
+```
+
+Any other pseudo class can be written as a raw value.
+
+```
+[nth-child(odd)]:border-c=[#111]
+```
+";
+
+const UNKNOWN_MEDIA_QUERY: &str = "\
+A media query variant was used that doesn't exist.
+
+```
+@medium:hidden
+```
+
+The media queries that can be used are the breakpoints of the theme, `sm`,
+`md`, `lg`, `xl` and `2xl` by default, and `dark`, `portrait`, `landscape`,
+`motion-safe`, `motion-reduce` and `print`.
+
+```
+@md:hidden
+```
+
+Every breakpoint also has a `max-` variant that applies below it, and two
+breakpoints form a range from the first up to the second.
+
+```
+@max-md:hidden
+@md-lg:hidden
+```
+
+A theme that sets `breakpoints` replaces the default ones, so `@md` only works
+if the theme has an `md` breakpoint.
+
+Any other media query can be written as a raw value.
+
+```
+@[(min-width: 1234px)]:(grid grid-c=2)
+```
+";
+
+const EMPTY_BREAKPOINT_RANGE: &str = "\
+A breakpoint range was used whose first breakpoint isn't below the second, so
+no screen width matches it.
+
+```
+@lg-md:hidden
+```
+
+Put the smaller breakpoint first.
+
+```
+@md-lg:hidden
+```
+";
+
+const UNEXPECTED_TOKEN: &str = "\
+The code doesn't follow the syntax.
+
+The basic syntax is
+
+```
+@mediaquery:pseudoclass:util=value
+```
+
+Groups are closed with `)` and a variant is followed by `:`.
+
+```
+hover:(p=1 m=2
+```
+
+```
+hover:(p=1 m=2)
+```
+";
+
+const MULTIPLE_VARIANT_GROUPS: &str = "\
+An expression can only have one variant group.
+
+```
+(hover focus):(@md @lg):p=(1 2)
+```
+
+Nest the expression to use another variant group. Only the latest variants will
+be matched.
+
+```
+(_ @md):(p=(0 10) (_ @md @lg):(m=(0 10 20)))
+```
+";
+
+const EMPTY_VALUE_GROUP: &str = "\
+A value group has no values.
+
+```
+(_ @md):p=()
+```
+
+A value group needs one value per variant. Use `_` to skip a variant.
+
+```
+(_ @md):p=(_ 2)
+```
+";
+
+const UNKNOWN_UTIL: &str = "\
+A util was used that doesn't exist.
+
+```
+paddin=1
+```
+
+See the docs for the utils that can be used.
+
+```
+p=1
+```
+
+Any other CSS can be written as raw CSS.
+
+```
+[border: 1px solid black]
+```
+";
+
+const INVALID_VALUE: &str = "\
+A util was given a value that it can't use.
+
+```
+bg-c=blurple
+```
+
+Use one of the values the util takes, or a raw value for anything else.
+
+```
+bg-c=[#5865f2]
+```
+";
+
+const UNEXPECTED_VALUE: &str = "\
+A util that doesn't take a value was given one.
+
+```
+hidden=1
+```
+
+Remove the value.
+
+```
+hidden
+```
+";
+
+const MISSING_VALUE: &str = "\
+A util that needs a value wasn't given one.
+
+```
+p
+```
+
+Assign a value with `=`.
+
+```
+p=1
+```
+";
+
+const REJECTED_BY_PLUGIN: &str = "\
+A util written in Rust by a plugin can't be used like this. The message comes
+from the plugin and says what it expects.
+
+```
+elevation=9
+```
+
+Use the util the way the plugin's documentation describes.
+
+```
+elevation=3
+```
+";
+
+const GROUP_LENGTH_MISMATCH: &str = "\
+A value group doesn't have the same number of values as the variant group it is
+matched against.
+
+```
+(_ @md):p=(1 2 3)
+```
+
+Every value is matched with the variant at the same position, so a value group
+needs one value per variant. Use `_` as the value to skip a variant, or assign a
+single value to skip the matching altogether.
+
+```
+(_ @md @lg):(p=(1 _ 3) m=10)
+```
+
+Nested utils are matched against the closest variant group above them.
+
+```
+(_ @md):(hover:p=(0 10) focus:m=(0 10))
+```
+";
+
+const MISSING_VARIANT_GROUP: &str = "\
+A value group was used without a variant group to match its values against.
+
+```
+p=(1 2)
+```
+
+Add a variant group, or assign a single value.
+
+```
+(_ @md):p=(1 2)
+```
+";
+
+const INVALID_SHORTCUT: &str = "\
+A shortcut was used whose ecss doesn't parse. The error is in the theme, not
+where the shortcut is used.
+
+```toml
+[shortcuts]
+card = \"p=6 bg-c=[white\"
+```
+
+Fix the shortcut in the theme.
+
+```toml
+[shortcuts]
+card = \"p=6 bg-c=white\"
+```
+";
+
+const RECURSIVE_SHORTCUT: &str = "\
+A shortcut uses itself, directly or through other shortcuts, so it can never be
+fully expanded.
+
+```toml
+[shortcuts]
+card = \"p=6 panel\"
+panel = \"border-w=1 card\"
+```
+
+Move the shared utils into a shortcut that both use.
+
+```toml
+[shortcuts]
+surface = \"p=6 border-w=1\"
+card = \"surface\"
+panel = \"surface\"
+```
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compiler::Compiler, diagnostic::Diagnostic, span::Span};
+
+    /// Code and span of every error in `code`
+    fn codes(code: &str) -> Vec<(&'static str, Span)> {
+        Compiler::new()
+            .generate_classes(code)
+            .unwrap_err()
+            .iter()
+            .map(|err| (err.code().as_str(), Diagnostic::from(err).span))
+            .collect()
+    }
+
+    #[test]
+    fn codes_round_trip() {
+        for code in ErrorCode::ALL {
+            assert_eq!(ErrorCode::from_code(code.as_str()), Some(*code));
+            assert!(!code.title().is_empty() && !code.explain().is_empty());
+        }
+        assert_eq!(
+            ErrorCode::from_code("E0101"),
+            Some(ErrorCode::UnknownPseudo)
+        );
+        assert_eq!(ErrorCode::from_code("E9999"), None);
+    }
+
+    #[test]
+    fn errors_have_codes() {
+        let cases = [
+            ("p=[1", vec![("E0001", Span::new(2, 3))]),
+            ("p=1 ^", vec![("E0002", Span::new(4, 5))]),
+            ("hovr:p=1", vec![("E0101", Span::new(0, 4))]),
+            ("@medium:p=1", vec![("E0102", Span::new(0, 7))]),
+            ("p=1)", vec![("E0201", Span::new(3, 4))]),
+            (
+                "(hover focus):(@md @lg):p=(1 2)",
+                vec![("E0202", Span::new(14, 15))],
+            ),
+            ("p=()", vec![("E0203", Span::new(2, 4))]),
+            ("foo=1", vec![("E0301", Span::new(0, 5))]),
+            ("p=banana", vec![("E0302", Span::new(2, 8))]),
+            ("block=1", vec![("E0303", Span::new(0, 7))]),
+            ("p", vec![("E0304", Span::new(0, 1))]),
+            (
+                "(hover focus):p=(1 2 3)",
+                vec![("E0401", Span::new(16, 23))],
+            ),
+            ("p=(1 2)", vec![("E0402", Span::new(2, 7))]),
+        ];
+        for (code, expected) in cases {
+            assert_eq!(codes(code), expected, "{}", code);
+        }
+    }
+}
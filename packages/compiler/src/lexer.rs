@@ -1,51 +1,107 @@
-use crate::token::Token;
+use std::fmt;
 
-pub fn get_tokens(str: &str) -> Vec<Token> {
-    let mut chars = str.chars().peekable();
+use crate::{
+    span::{Span, Spanned},
+    token::Token,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum LexErrorKind {
+    /// A `[` without a matching `]`
+    UnterminatedRawValue,
+
+    /// A char that can't start any token
+    UnknownChar,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LexError {
+    pub kind: LexErrorKind,
+
+    /// Byte offset of `char` in the source
+    pub offset: usize,
+
+    pub char: char,
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            LexErrorKind::UnterminatedRawValue => {
+                write!(f, "Expected ] to close [ at offset {}", self.offset)
+            }
+            LexErrorKind::UnknownChar => {
+                write!(f, "Unknown char: {} at offset {}", self.char, self.offset)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LexError {}
+
+/// Bad chars are skipped and reported so the rest of the input still gets tokenized
+pub fn get_tokens(str: &str) -> (Vec<Spanned<Token>>, Vec<LexError>) {
+    let mut chars = str.char_indices().peekable();
     let mut tokens = Vec::new();
+    let mut errors = Vec::new();
 
-    while let Some(char) = chars.next() {
-        match char {
-            '@' => tokens.push(Token::At),
-            ':' => tokens.push(Token::Colon),
-            '=' => tokens.push(Token::Eq),
-            '(' => tokens.push(Token::LParen),
-            ')' => tokens.push(Token::RParen),
-            '_' => tokens.push(Token::Underscore),
+    while let Some((offset, char)) = chars.next() {
+        let token = match char {
+            '@' => Token::At,
+            ':' => Token::Colon,
+            '=' => Token::Eq,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '_' => Token::Underscore,
 
-            // Raw value
+            // Raw value, an unterminated one runs to the end
             '[' => {
                 let mut raw_value = String::new();
                 loop {
                     match chars.next() {
-                        Some(']') => break,
-                        Some(char) => raw_value.push(char),
-                        None => panic!("Expected ]"),
+                        Some((_, ']')) => break,
+                        Some((_, char)) => raw_value.push(char),
+                        None => {
+                            errors.push(LexError {
+                                kind: LexErrorKind::UnterminatedRawValue,
+                                offset,
+                                char,
+                            });
+                            break;
+                        }
                     }
                 }
-                tokens.push(Token::RawValue(raw_value));
+                Token::RawValue(raw_value)
             }
 
             // Word
             char if char.is_alphanumeric() || char == '-' => {
                 let mut word = String::from(char);
-                while let Some(char) = chars.peek() {
+                while let Some((_, char)) = chars.peek() {
                     if char.is_alphanumeric() || *char == '-' || *char == '.' || *char == '/' {
-                        word.push(chars.next().unwrap());
+                        word.push(chars.next().unwrap().1);
                     } else {
                         break;
                     }
                 }
-                tokens.push(Token::Word(word));
+                Token::Word(word)
             }
 
-            c if c.is_whitespace() => {
-                tokens.push(Token::Whitespace);
+            c if c.is_whitespace() => Token::Whitespace,
+
+            _ => {
+                errors.push(LexError {
+                    kind: LexErrorKind::UnknownChar,
+                    offset,
+                    char,
+                });
+                continue;
             }
+        };
 
-            _ => panic!("Unknown char: {}", char),
-        }
+        let end = chars.peek().map_or(str.len(), |(offset, _)| *offset);
+        tokens.push(Spanned::new(token, Span::new(offset, end)));
     }
 
-    tokens
+    (tokens, errors)
 }
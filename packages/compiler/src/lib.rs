@@ -1,7 +1,17 @@
 mod ast;
+mod class_naming;
 pub mod compiler;
 mod css_writer;
+pub mod diagnostic;
+pub mod error;
+mod error_code;
 mod lexer;
+pub mod manifest;
 mod parser;
+pub mod source_map;
+mod span;
+pub mod theme;
 mod token;
 mod utils;
+/// Value fns of the built-in utils, for utils registered from outside the crate
+pub mod value_fns;
@@ -0,0 +1,82 @@
+use std::{fmt, fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+/// The class names given out so far. Loading it before generating classes makes
+/// the same util get the same name across builds and build workers.
+///
+/// ```json
+/// {
+///   "class_count": 2,
+///   "classes": [
+///     { "index": 0, "name": "_E0", "source": "p=1" },
+///     { "index": 1, "name": "_E1", "source": "@md:hover:bg-c=blue-500" }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Index of the next new class, so new names never reuse old ones
+    pub class_count: u32,
+
+    /// Ordered by `index`
+    pub classes: Vec<ManifestClass>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestClass {
+    /// Order of registration, breaks ties in the cascade
+    pub index: u32,
+
+    pub name: String,
+
+    /// The util written as ecss, `@md:hover:bg-c=blue-500`
+    pub source: String,
+}
+
+#[derive(Debug)]
+pub enum ManifestError {
+    Io(io::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ManifestError::Io(err) => write!(f, "Couldn't access manifest: {}", err),
+            ManifestError::Json(err) => write!(f, "Invalid manifest: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+impl From<io::Error> for ManifestError {
+    fn from(err: io::Error) -> Self {
+        ManifestError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for ManifestError {
+    fn from(err: serde_json::Error) -> Self {
+        ManifestError::Json(err)
+    }
+}
+
+impl Manifest {
+    pub fn from_json(json: &str) -> Result<Self, ManifestError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Manifest is always valid JSON")
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ManifestError> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ManifestError> {
+        Ok(fs::write(path, self.to_json())?)
+    }
+}
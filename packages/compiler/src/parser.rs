@@ -1,27 +1,104 @@
-use crate::{ast::*, token::Token};
-use std::{iter::Peekable, vec::IntoIter};
+use crate::{
+    ast::*,
+    span::{Span, Spanned},
+    token::{Token, TokenKind},
+};
+use std::{fmt, iter::Peekable, vec::IntoIter};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ParseErrorKind {
+    /// Found a token other than the expected ones
+    UnexpectedToken,
+
+    /// `(hover focus):(@md @lg):p=(1 2)`
+    MultipleVariantGroups,
+
+    /// `p=()`
+    EmptyValueGroup,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub span: Span,
+    pub expected: Vec<TokenKind>,
+
+    /// `None` when the end of the input was reached
+    pub found: Option<Token>,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::UnexpectedToken => {
+                let found = match &self.found {
+                    Some(token) => token.to_string(),
+                    None => String::from("end of input"),
+                };
+                match self.expected.split_last() {
+                    None => write!(f, "Unexpected {}", found),
+                    Some((last, [])) => write!(f, "Expected {}, found {}", last, found),
+                    Some((last, rest)) => {
+                        let rest: Vec<String> = rest.iter().map(|kind| kind.to_string()).collect();
+                        write!(
+                            f,
+                            "Expected {} or {}, found {}",
+                            rest.join(", "),
+                            last,
+                            found
+                        )
+                    }
+                }
+            }
+            ParseErrorKind::MultipleVariantGroups => f.write_str("Only one variant group allowed"),
+            ParseErrorKind::EmptyValueGroup => f.write_str("Value is empty"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
 
 pub struct Parser {
-    tokens: Peekable<IntoIter<Token>>,
+    tokens: Peekable<IntoIter<Spanned<Token>>>,
+
+    /// Errors the parser has recovered from
+    errors: Vec<ParseError>,
+
+    /// End of the last consumed token
+    prev_end: usize,
+
+    /// Empty span at the end of the input
+    eof: Span,
 }
 
-impl From<Vec<Token>> for Parser {
-    fn from(tokens: Vec<Token>) -> Self {
+impl From<Vec<Spanned<Token>>> for Parser {
+    fn from(tokens: Vec<Spanned<Token>>) -> Self {
+        let end = tokens.last().map_or(0, |token| token.span.end);
         Self {
             tokens: tokens.into_iter().peekable(),
+            errors: Vec::new(),
+            prev_end: 0,
+            eof: Span::new(end, end),
         }
     }
 }
 
 impl Parser {
-    pub fn parse(&mut self) -> Vec<Expr> {
-        let exprs = self.parse_exprs();
+    /// Returns the expressions that could be parsed together with every error.
+    /// Malformed expressions are skipped up to the next whitespace or the `)`
+    /// closing the current group.
+    pub fn parse(&mut self) -> (Vec<Expr>, Vec<ParseError>) {
+        let mut exprs = self.parse_exprs();
 
-        if let Some(token) = self.tokens.next() {
-            panic!("Unexpected token in parse {:?}", token);
+        // parse_exprs stops at tokens that can't start an expression
+        while let Some(token) = self.next() {
+            let err = self.unexpected(Some(token), &[]);
+            self.errors.push(err);
+            self.synchronize();
+            exprs.append(&mut self.parse_exprs());
         }
 
-        exprs
+        (exprs, std::mem::take(&mut self.errors))
     }
 
     fn parse_exprs(&mut self) -> Vec<Expr> {
@@ -30,87 +107,144 @@ impl Parser {
         loop {
             self.eat_whitespaces();
             match self.parse_expr() {
-                Some(expr) => {
+                Ok(Some(expr)) => {
                     expressions.push(expr);
                 }
-                None => break,
+                Ok(None) => break,
+                Err(err) => {
+                    self.errors.push(err);
+                    self.synchronize();
+                }
             }
         }
 
         expressions
     }
 
-    fn parse_expr(&mut self) -> Option<Expr> {
-        let (variants, variant_group) = self.parse_variants();
+    /// Exprs of a group, eats the closing `)`
+    fn parse_group_exprs(&mut self) -> Vec<Expr> {
+        let mut exprs = self.parse_exprs();
 
-        if variants.is_empty() && variant_group.is_empty() {
-            match self.tokens.peek() {
+        loop {
+            match self.next() {
+                Some((Token::RParen, _)) => break,
+                None => {
+                    let err = self.unexpected(None, &[TokenKind::RParen]);
+                    self.errors.push(err);
+                    break;
+                }
+                token => {
+                    let err = self.unexpected(token, &[TokenKind::RParen]);
+                    self.errors.push(err);
+                    self.synchronize();
+                    exprs.append(&mut self.parse_exprs());
+                }
+            }
+        }
+
+        exprs
+    }
+
+    /// Skip to the next whitespace or `)` outside of nested parens
+    fn synchronize(&mut self) {
+        let mut depth = 0;
+        while let Some(token) = self.peek() {
+            match token {
+                Token::Whitespace if depth == 0 => break,
+                Token::RParen if depth == 0 => break,
+                Token::RParen => depth -= 1,
+                Token::LParen => depth += 1,
+                _ => {}
+            }
+            self.next();
+        }
+    }
+
+    fn parse_expr(&mut self) -> Result<Option<Expr>, ParseError> {
+        let (variants, variant_group) = self.parse_variants()?;
+
+        if variants.is_empty() && variant_group.is_none() {
+            match self.peek() {
                 Some(Token::RawValue(_)) => {
-                    if let Some(Token::RawValue(raw_css)) = self.tokens.next() {
-                        return Some(Expr::RawCss(raw_css));
+                    if let Some((Token::RawValue(raw_css), span)) = self.next() {
+                        return Ok(Some(Expr::RawCss(Spanned::new(raw_css, span))));
                     }
                     unreachable!()
                 }
-                _ => Some(Expr::Util(self.parse_util()?)),
+                _ => Ok(self.parse_util()?.map(Expr::Util)),
             }
         } else {
-            match self.tokens.peek() {
+            match self.peek() {
                 Some(Token::LParen) => {
                     // Eat (
-                    self.tokens.next();
-                    let exprs = self.parse_exprs();
-                    // Eat )
-                    self.assert_next(Token::RParen);
-                    Some(Expr::Variant {
+                    self.next();
+                    let exprs = self.parse_group_exprs();
+                    Ok(Some(Expr::Variant {
                         variants,
                         variant_group,
                         exprs,
-                    })
+                    }))
                 }
 
-                _ => Some(Expr::Variant {
-                    variants,
-                    variant_group,
-                    exprs: vec![self.parse_expr().expect("Expected expr")],
-                }),
+                _ => match self.parse_expr()? {
+                    Some(expr) => Ok(Some(Expr::Variant {
+                        variants,
+                        variant_group,
+                        exprs: vec![expr],
+                    })),
+                    None => Err(self.unexpected_next(&[
+                        TokenKind::Word,
+                        TokenKind::RawValue,
+                        TokenKind::LParen,
+                    ])),
+                },
             }
         }
     }
 
-    fn parse_util(&mut self) -> Option<Util> {
-        let properties = self.parse_properties()?;
-        let tree = match self.tokens.peek() {
+    fn parse_util(&mut self) -> Result<Option<Util>, ParseError> {
+        let start = self.position();
+        let properties = match self.parse_properties()? {
+            Some(properties) => properties,
+            None => return Ok(None),
+        };
+        let tree = match self.peek() {
             // Branch, multi util
             Some(Token::LParen) => {
                 // Eat (
-                self.tokens.next();
-                let tree = UtilTree::Branch(self.parse_exprs());
-                // Eat )
-                self.assert_next(Token::RParen);
-                tree
+                self.next();
+                UtilTree::Branch(self.parse_group_exprs())
             }
 
             // Leaf, assignment
             Some(Token::Eq) => {
                 // Eat =
-                self.tokens.next();
-                UtilTree::Leaf(Some(self.parse_value()))
+                self.next();
+                UtilTree::Leaf(Some(self.parse_value()?))
             }
 
             // Util without assignment
             _ => UtilTree::Leaf(None),
         };
 
-        Some(Util { properties, tree })
+        Ok(Some(Util {
+            properties,
+            tree,
+            span: self.span_from(start),
+        }))
     }
 
-    fn parse_variants(&mut self) -> (Vec<Variant>, Vec<Option<Variant>>) {
+    fn parse_variants(
+        &mut self,
+    ) -> Result<(Vec<Spanned<Variant>>, Option<VariantGroup>), ParseError> {
         let mut variants = Vec::new();
         let mut is_variant_group = false;
         let mut variant_group = Vec::new();
+        // Start of the variant group, then its full span once closed
+        let mut variant_group_span: Option<Span> = None;
         loop {
             self.eat_whitespaces();
-            match self.tokens.peek() {
+            match self.peek() {
                 // Pseudo
                 Some(Token::Word(_)) => {
                     if !is_variant_group {
@@ -120,13 +254,14 @@ impl Parser {
                         }
                     }
 
-                    if let Some(Token::Word(word)) = self.tokens.next() {
+                    if let Some((Token::Word(word), span)) = self.next() {
+                        let variant = Variant::Pseudo(Value::Iden(word));
                         if is_variant_group {
-                            variant_group.push(Some(Variant::Pseudo(Value::Iden(word))));
+                            variant_group.push(Spanned::new(Some(variant), span));
                         } else {
-                            variants.push(Variant::Pseudo(Value::Iden(word)));
+                            variants.push(Spanned::new(variant, span));
                             // Eat :
-                            self.tokens.next();
+                            self.next();
                         }
                     }
                 }
@@ -137,54 +272,58 @@ impl Parser {
                             _ => break, // Is util
                         }
                     }
-                    if let Some(Token::RawValue(raw)) = self.tokens.next() {
+                    if let Some((Token::RawValue(raw), span)) = self.next() {
+                        let variant = Variant::Pseudo(Value::Raw(raw));
                         if is_variant_group {
-                            variant_group.push(Some(Variant::Pseudo(Value::Raw(raw))));
+                            variant_group.push(Spanned::new(Some(variant), span));
                         } else {
-                            variants.push(Variant::Pseudo(Value::Raw(raw)));
+                            variants.push(Spanned::new(variant, span));
                             // Eat :
-                            self.tokens.next();
+                            self.next();
                         }
                     }
                 }
                 // Media
                 Some(Token::At) => {
+                    let start = self.position();
                     // Eat @
-                    self.tokens.next();
-                    match self.tokens.next() {
-                        Some(Token::Word(word)) => {
-                            if is_variant_group {
-                                variant_group.push(Some(Variant::Media(Value::Iden(word))));
-                            } else {
-                                variants.push(Variant::Media(Value::Iden(word)));
-                                self.assert_next(Token::Colon);
-                            }
-                        }
-                        Some(Token::RawValue(raw)) => {
-                            if is_variant_group {
-                                variant_group.push(Some(Variant::Media(Value::Raw(raw))));
-                            } else {
-                                variants.push(Variant::Media(Value::Raw(raw)));
-                                self.assert_next(Token::Colon);
-                            }
+                    self.next();
+                    let variant = match self.next() {
+                        Some((Token::Word(word), _)) => Variant::Media(Value::Iden(word)),
+                        Some((Token::RawValue(raw), _)) => Variant::Media(Value::Raw(raw)),
+                        token => {
+                            return Err(
+                                self.unexpected(token, &[TokenKind::Word, TokenKind::RawValue])
+                            )
                         }
-                        _ => panic!("Expected word or raw value"),
+                    };
+                    let span = self.span_from(start);
+                    if is_variant_group {
+                        variant_group.push(Spanned::new(Some(variant), span));
+                    } else {
+                        variants.push(Spanned::new(variant, span));
+                        self.assert_next(Token::Colon)?;
                     }
                 }
                 Some(Token::Underscore) if is_variant_group => {
                     // Eat _
-                    self.tokens.next();
-                    variant_group.push(None);
+                    if let Some((_, span)) = self.next() {
+                        variant_group.push(Spanned::new(None, span));
+                    }
                 }
                 Some(Token::LParen) if !is_variant_group => {
-                    match self.peek_after_token(Token::RParen) {
+                    match self.peek_after_variant_group() {
                         Some(Token::Colon) => {
                             // Is variant group
-                            if !variant_group.is_empty() {
-                                panic!("Only one variant group allowed");
+                            if variant_group_span.is_some() {
+                                let mut err = self.unexpected_next(&[]);
+                                err.kind = ParseErrorKind::MultipleVariantGroups;
+                                return Err(err);
                             }
                             // Eat (
-                            self.tokens.next();
+                            let start = self.position();
+                            self.next();
+                            variant_group_span = Some(Span::new(start, start));
                             is_variant_group = true;
                         }
                         _ => break,
@@ -192,9 +331,12 @@ impl Parser {
                 }
                 Some(Token::RParen) if is_variant_group => {
                     // eat )
-                    self.tokens.next();
+                    self.next();
+                    if let Some(span) = &mut variant_group_span {
+                        span.end = self.prev_end;
+                    }
                     is_variant_group = false;
-                    self.assert_next(Token::Colon);
+                    self.assert_next(Token::Colon)?;
                 }
                 _ => break,
             }
@@ -202,107 +344,454 @@ impl Parser {
 
         // Hasn't closed group
         if is_variant_group {
-            panic!("Expected )")
+            return Err(self.unexpected_next(&[
+                TokenKind::Word,
+                TokenKind::RawValue,
+                TokenKind::At,
+                TokenKind::Underscore,
+                TokenKind::RParen,
+            ]));
         }
 
-        (variants, variant_group)
+        let variant_group = variant_group_span.map(|span| VariantGroup {
+            variants: variant_group,
+            span,
+        });
+
+        Ok((variants, variant_group))
     }
 
-    fn parse_properties(&mut self) -> Option<Vec<String>> {
-        match self.tokens.peek() {
+    fn parse_properties(&mut self) -> Result<Option<Vec<Spanned<String>>>, ParseError> {
+        match self.peek() {
             Some(Token::Word(_)) => {
-                if let Some(Token::Word(iden)) = self.tokens.next() {
-                    return Some(vec![iden]);
+                if let Some((Token::Word(iden), span)) = self.next() {
+                    return Ok(Some(vec![Spanned::new(iden, span)]));
                 }
                 unreachable!()
             }
             Some(Token::LParen) => {
                 // Eat (
-                self.tokens.next();
+                self.next();
                 let mut properties = Vec::new();
 
                 loop {
                     self.eat_whitespaces();
-                    match self.tokens.next() {
-                        Some(Token::Word(iden)) => {
-                            properties.push(iden);
+                    match self.next() {
+                        Some((Token::Word(iden), span)) => {
+                            properties.push(Spanned::new(iden, span));
                         }
-                        Some(Token::RParen) => {
+                        Some((Token::RParen, _)) => {
                             break;
                         }
-                        token => panic!("parse_properties unexpected token: {:?}", token),
+                        token => {
+                            return Err(
+                                self.unexpected(token, &[TokenKind::Word, TokenKind::RParen])
+                            )
+                        }
                     }
                 }
 
-                Some(properties)
+                Ok(Some(properties))
             }
-            _ => None,
+            _ => Ok(None),
         }
     }
 
-    fn parse_value(&mut self) -> ValueOrGroup {
-        match self.tokens.next() {
-            Some(Token::RawValue(raw_value)) => ValueOrGroup::Value(Value::Raw(raw_value)),
-            Some(Token::Word(word)) => ValueOrGroup::Value(Value::Iden(word)),
-            Some(Token::LParen) => {
+    fn parse_value(&mut self) -> Result<Spanned<ValueOrGroup>, ParseError> {
+        match self.next() {
+            Some((Token::RawValue(raw_value), span)) => Ok(Spanned::new(
+                ValueOrGroup::Value(Value::Raw(raw_value)),
+                span,
+            )),
+            Some((Token::Word(word), span)) => {
+                Ok(Spanned::new(ValueOrGroup::Value(Value::Iden(word)), span))
+            }
+            Some((Token::LParen, start)) => {
                 let mut values = Vec::new();
 
-                loop {
+                let end = loop {
                     self.eat_whitespaces();
-                    match self.tokens.next() {
-                        Some(Token::RawValue(raw_value)) => {
-                            values.push(Some(Value::Raw(raw_value)));
+                    match self.next() {
+                        Some((Token::RawValue(raw_value), span)) => {
+                            values.push(Spanned::new(Some(Value::Raw(raw_value)), span));
+                        }
+                        Some((Token::Word(word), span)) => {
+                            values.push(Spanned::new(Some(Value::Iden(word)), span));
                         }
-                        Some(Token::Word(word)) => {
-                            values.push(Some(Value::Iden(word)));
+                        Some((Token::Underscore, span)) => {
+                            values.push(Spanned::new(None, span));
                         }
-                        Some(Token::Underscore) => {
-                            values.push(None);
+                        Some((Token::RParen, span)) => break span,
+                        token => {
+                            return Err(self.unexpected(
+                                token,
+                                &[
+                                    TokenKind::Word,
+                                    TokenKind::RawValue,
+                                    TokenKind::Underscore,
+                                    TokenKind::RParen,
+                                ],
+                            ))
                         }
-                        Some(Token::RParen) => break,
-                        token => panic!("Unexpected token while parsing value group: {:?}", token),
                     }
-                }
+                };
 
                 if values.is_empty() {
-                    panic!("Value is empty.");
+                    return Err(ParseError {
+                        kind: ParseErrorKind::EmptyValueGroup,
+                        span: start.to(end),
+                        expected: vec![TokenKind::Word, TokenKind::RawValue, TokenKind::Underscore],
+                        found: Some(Token::RParen),
+                    });
                 }
 
-                ValueOrGroup::Group(values)
+                Ok(Spanned::new(ValueOrGroup::Group(values), start.to(end)))
             }
-            _ => panic!("Failed to parse value"),
+            token => Err(self.unexpected(
+                token,
+                &[TokenKind::Word, TokenKind::RawValue, TokenKind::LParen],
+            )),
         }
     }
 
     fn eat_whitespaces(&mut self) {
-        while let Some(Token::Whitespace) = self.tokens.peek() {
-            self.tokens.next();
+        while let Some(Token::Whitespace) = self.peek() {
+            self.next();
         }
     }
 
-    fn assert_next(&mut self, token: Token) {
-        match self.tokens.next() {
-            Some(c) => {
-                assert_eq!(c, token);
-            }
-            _ => panic!("Expected {:?}", token),
+    fn assert_next(&mut self, token: Token) -> Result<(), ParseError> {
+        match self.next() {
+            Some((next, _)) if next == token => Ok(()),
+            next => Err(self.unexpected(next, &[token.kind()])),
+        }
+    }
+
+    fn unexpected(&self, found: Option<(Token, Span)>, expected: &[TokenKind]) -> ParseError {
+        let (span, found) = match found {
+            Some((token, span)) => (span, Some(token)),
+            None => (self.eof, None),
+        };
+        ParseError {
+            kind: ParseErrorKind::UnexpectedToken,
+            span,
+            expected: expected.to_vec(),
+            found,
         }
     }
 
+    /// Error for the next token, without consuming it
+    fn unexpected_next(&mut self, expected: &[TokenKind]) -> ParseError {
+        let found = self
+            .tokens
+            .peek()
+            .map(|token| (token.node.clone(), token.span));
+        self.unexpected(found, expected)
+    }
+
+    /// Start of the next token
+    fn position(&mut self) -> usize {
+        self.tokens
+            .peek()
+            .map_or(self.eof.start, |token| token.span.start)
+    }
+
+    /// Span from `start` to the end of the last consumed token
+    fn span_from(&self, start: usize) -> Span {
+        Span::new(start, self.prev_end.max(start))
+    }
+
+    fn peek(&mut self) -> Option<&Token> {
+        self.tokens.peek().map(|token| &token.node)
+    }
+
+    fn next(&mut self) -> Option<(Token, Span)> {
+        let token = self.tokens.next()?;
+        self.prev_end = token.span.end;
+        Some((token.node, token.span))
+    }
+
     fn peek_nth(&self, nth: usize) -> Option<Token> {
-        self.tokens.clone().nth(nth)
+        self.tokens.clone().nth(nth).map(|token| token.node)
     }
 
-    fn peek_after_token(&self, after_token: Token) -> Option<Token> {
-        let mut tokens = self.tokens.clone();
+    /// Token after the `)` closing the `(` at the current position. `None` if
+    /// the group contains another group, which a variant group never does
+    fn peek_after_variant_group(&self) -> Option<Token> {
+        let mut tokens = self.tokens.clone().skip(1);
         loop {
-            if let Some(token) = tokens.next() {
-                if token == after_token {
-                    return tokens.next();
+            match tokens.next()?.node {
+                Token::RParen => return tokens.next().map(|token| token.node),
+                Token::LParen => return None,
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        compiler::{Compiler, CompilerOptions, ErrorPolicy},
+        lexer::get_tokens,
+    };
+
+    /// The AST written back as ecss, with groups in parens
+    fn show(exprs: &[Expr]) -> String {
+        let exprs: Vec<String> = exprs.iter().map(show_expr).collect();
+        exprs.join(" ")
+    }
+
+    fn show_expr(expr: &Expr) -> String {
+        match expr {
+            Expr::Variant {
+                variants,
+                variant_group,
+                exprs,
+            } => {
+                let mut shown = String::new();
+                if let Some(group) = variant_group {
+                    let group: Vec<String> = group
+                        .variants
+                        .iter()
+                        .map(|variant| {
+                            variant
+                                .node
+                                .as_ref()
+                                .map_or(String::from("_"), Variant::to_string)
+                        })
+                        .collect();
+                    shown.push_str(&format!("({}):", group.join(" ")));
+                }
+                for variant in variants {
+                    shown.push_str(&format!("{}:", variant.node));
                 }
-            } else {
-                return None;
+                format!("{}({})", shown, show(exprs))
             }
+            Expr::Util(util) => {
+                let properties: Vec<&str> = util
+                    .properties
+                    .iter()
+                    .map(|property| property.node.as_str())
+                    .collect();
+                let properties = properties.join("|");
+                match &util.tree {
+                    UtilTree::Leaf(None) => properties,
+                    UtilTree::Leaf(Some(value)) => match &value.node {
+                        ValueOrGroup::Value(value) => format!("{}={}", properties, value),
+                        ValueOrGroup::Group(values) => {
+                            let values: Vec<String> = values
+                                .iter()
+                                .map(|value| {
+                                    value
+                                        .node
+                                        .as_ref()
+                                        .map_or(String::from("_"), Value::to_string)
+                                })
+                                .collect();
+                            format!("{}=({})", properties, values.join(" "))
+                        }
+                    },
+                    UtilTree::Branch(exprs) => format!("{}({})", properties, show(exprs)),
+                }
+            }
+            Expr::RawCss(raw) => format!("[{}]", raw.node),
         }
     }
+
+    fn parse(code: &str) -> (String, Vec<(ParseErrorKind, usize, usize, String)>) {
+        let (tokens, lex_errors) = get_tokens(code);
+        assert!(lex_errors.is_empty());
+        let (exprs, errors) = Parser::from(tokens).parse();
+        let errors = errors
+            .into_iter()
+            .map(|err| {
+                (
+                    err.kind.clone(),
+                    err.span.start,
+                    err.span.end,
+                    err.to_string(),
+                )
+            })
+            .collect();
+        (show(&exprs), errors)
+    }
+
+    #[test]
+    fn parses_without_errors() {
+        assert_eq!(
+            parse("hover:(p=1 @md:m=2) bg-c=red-500 [color:red]"),
+            (
+                String::from("hover:(p=1 @md:(m=2)) bg-c=red-500 [color:red]"),
+                vec![]
+            )
+        );
+        assert_eq!(
+            parse("(hover _):p=(1 2) border-(c=red-500 w=2)"),
+            (
+                String::from("(hover _):(p=(1 2)) border-(c=red-500 w=2)"),
+                vec![]
+            )
+        );
+    }
+
+    #[test]
+    fn skips_to_next_whitespace() {
+        assert_eq!(
+            parse("p=1 hover:=) m=2"),
+            (
+                String::from("p=1 m=2"),
+                vec![
+                    (
+                        ParseErrorKind::UnexpectedToken,
+                        10,
+                        11,
+                        String::from("Expected word, raw value or `(`, found `=`")
+                    ),
+                    (
+                        ParseErrorKind::UnexpectedToken,
+                        11,
+                        12,
+                        String::from("Unexpected `)`")
+                    ),
+                ]
+            )
+        );
+        assert_eq!(
+            parse("(p=1 hover:m=2"),
+            (
+                String::from("hover:(m=2)"),
+                vec![(
+                    ParseErrorKind::UnexpectedToken,
+                    2,
+                    3,
+                    String::from("Expected word or `)`, found `=`")
+                )]
+            )
+        );
+        assert_eq!(
+            parse("p=() m=1"),
+            (
+                String::from("m=1"),
+                vec![(
+                    ParseErrorKind::EmptyValueGroup,
+                    2,
+                    4,
+                    String::from("Value is empty")
+                )]
+            )
+        );
+        assert_eq!(
+            parse("(hover focus):(@md @lg):p=(1 2) m=1"),
+            (
+                String::from("m=1"),
+                vec![(
+                    ParseErrorKind::MultipleVariantGroups,
+                    14,
+                    15,
+                    String::from("Only one variant group allowed")
+                )]
+            )
+        );
+    }
+
+    #[test]
+    fn skips_nested_parens() {
+        assert_eq!(
+            parse("p=1 m=(2 hover:(p=3 =4) bg-c=red-500"),
+            (
+                String::from("p=1 bg-c=red-500"),
+                vec![(
+                    ParseErrorKind::UnexpectedToken,
+                    14,
+                    15,
+                    String::from("Expected word, raw value, `_` or `)`, found `:`")
+                )]
+            )
+        );
+    }
+
+    #[test]
+    fn skips_rest_of_unexpected_tokens() {
+        assert_eq!(
+            parse("p=1 =2 m=3"),
+            (
+                String::from("p=1 m=3"),
+                vec![(
+                    ParseErrorKind::UnexpectedToken,
+                    4,
+                    5,
+                    String::from("Unexpected `=`")
+                )]
+            )
+        );
+        assert_eq!(
+            parse("a=b=c m=3"),
+            (
+                String::from("a=b m=3"),
+                vec![(
+                    ParseErrorKind::UnexpectedToken,
+                    3,
+                    4,
+                    String::from("Unexpected `=`")
+                )]
+            )
+        );
+        assert_eq!(
+            parse("(hover _):(p m)=(1 2) m=3"),
+            (
+                String::from("(hover _):(p m) m=3"),
+                vec![(
+                    ParseErrorKind::UnexpectedToken,
+                    15,
+                    16,
+                    String::from("Unexpected `=`")
+                )]
+            )
+        );
+    }
+
+    #[test]
+    fn recovers_inside_groups() {
+        assert_eq!(
+            parse("hover:(p=1 =2 m=3) p=4"),
+            (
+                String::from("hover:(p=1 m=3) p=4"),
+                vec![(
+                    ParseErrorKind::UnexpectedToken,
+                    11,
+                    12,
+                    String::from("Expected `)`, found `=`")
+                )]
+            )
+        );
+        assert_eq!(
+            parse("hover:(p=1"),
+            (
+                String::from("hover:(p=1)"),
+                vec![(
+                    ParseErrorKind::UnexpectedToken,
+                    10,
+                    10,
+                    String::from("Expected `)`, found end of input")
+                )]
+            )
+        );
+    }
+
+    #[test]
+    fn lenient_compiles_what_was_parsed() {
+        let mut compiler = Compiler::with_options(CompilerOptions {
+            error_policy: ErrorPolicy::Lenient,
+            ..Default::default()
+        });
+        compiler
+            .generate_classes("p=1 m=(2 hover:(p=3 =4) bg-c=red-500 hover:(m=2")
+            .unwrap();
+        assert_eq!(
+            compiler.get_css(),
+            "._E0{padding:0.25rem;}._E1{background-color:#ef4444;}._E2:hover{margin:0.5rem;}"
+        );
+    }
 }
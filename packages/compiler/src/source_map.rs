@@ -0,0 +1,274 @@
+use crate::span::Span;
+
+/// Where a piece of ecss code starts in its file, like the value of a JSX
+/// attribute. Lines and columns start at 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceOrigin {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A position in one of the source files. Columns are in UTF-16 code units,
+/// like browsers expect
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SourcePosition {
+    /// Index into the source files
+    pub source: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Position of `span` when `code` starts at `origin`
+pub(crate) fn locate(
+    code: &str,
+    span: Span,
+    origin: &SourceOrigin,
+    source: usize,
+) -> SourcePosition {
+    let before = &code[..span.start.min(code.len())];
+    match before.rfind('\n') {
+        Some(line_start) => SourcePosition {
+            source,
+            line: origin.line + before.matches('\n').count(),
+            column: utf16_len(&before[line_start + 1..]),
+        },
+        None => SourcePosition {
+            source,
+            line: origin.line,
+            column: origin.column + utf16_len(before),
+        },
+    }
+}
+
+/// Source Map v3 of `css`. `mappings` are byte offsets into `css` in ascending
+/// order, each mapped to where it came from
+pub(crate) fn source_map(
+    css_file: &str,
+    css: &str,
+    sources: &[String],
+    mappings: &[(usize, SourcePosition)],
+) -> String {
+    let mut encoded = String::new();
+    let mut line_start = 0;
+    let mut first_in_line = true;
+    let mut prev_column = 0;
+    let mut prev = SourcePosition {
+        source: 0,
+        line: 0,
+        column: 0,
+    };
+    for (offset, position) in mappings {
+        let newlines = css[line_start..*offset].matches('\n').count();
+        if newlines > 0 {
+            line_start = css[..*offset].rfind('\n').map_or(0, |index| index + 1);
+            encoded.push_str(&";".repeat(newlines));
+            first_in_line = true;
+            prev_column = 0;
+        }
+        if !first_in_line {
+            encoded.push(',');
+        }
+        first_in_line = false;
+        let column = utf16_len(&css[line_start..*offset]);
+
+        // Every field is relative to the previous segment, the column only
+        // within the line
+        encode_vlq(column as i64 - prev_column as i64, &mut encoded);
+        encode_vlq(position.source as i64 - prev.source as i64, &mut encoded);
+        encode_vlq(position.line as i64 - prev.line as i64, &mut encoded);
+        encode_vlq(position.column as i64 - prev.column as i64, &mut encoded);
+        prev_column = column;
+        prev = *position;
+    }
+
+    let sources: Vec<String> = sources.iter().map(|source| json_string(source)).collect();
+    format!(
+        r#"{{"version":3,"file":{},"sources":[{}],"names":[],"mappings":{}}}"#,
+        json_string(css_file),
+        sources.join(","),
+        json_string(&encoded)
+    )
+}
+
+fn utf16_len(str: &str) -> usize {
+    str.chars().map(char::len_utf16).sum()
+}
+
+/// Base 64 VLQ, the sign is the lowest bit
+fn encode_vlq(value: i64, out: &mut String) {
+    const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut vlq = if value < 0 {
+        ((-value) << 1) | 1
+    } else {
+        value << 1
+    };
+    loop {
+        let mut digit = vlq & 0b11111;
+        vlq >>= 5;
+        if vlq > 0 {
+            digit |= 0b100000;
+        }
+        out.push(BASE64[digit as usize] as char);
+        if vlq == 0 {
+            break;
+        }
+    }
+}
+
+fn json_string(str: &str) -> String {
+    let mut json = String::from('"');
+    for char in str.chars() {
+        match char {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            char if char.is_control() => json.push_str(&format!("\\u{:04x}", char as u32)),
+            char => json.push(char),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::Compiler;
+
+    fn decode_vlq(chars: &mut impl Iterator<Item = u8>) -> i64 {
+        const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+        let (mut vlq, mut shift) = (0, 0);
+        loop {
+            let char = chars.next().unwrap();
+            let digit = BASE64.iter().position(|&c| c == char).unwrap() as i64;
+            vlq |= (digit & 0b11111) << shift;
+            shift += 5;
+            if digit & 0b100000 == 0 {
+                break;
+            }
+        }
+        if vlq & 1 == 1 {
+            -(vlq >> 1)
+        } else {
+            vlq >> 1
+        }
+    }
+
+    /// Absolute `[line, column, source, source line, source column]` of every
+    /// segment
+    fn decode_mappings(mappings: &str) -> Vec<[i64; 5]> {
+        let mut decoded = Vec::new();
+        let mut prev = [0; 5];
+        for (line, segments) in mappings.split(';').enumerate() {
+            prev[1] = 0;
+            for segment in segments.split(',').filter(|segment| !segment.is_empty()) {
+                let mut chars = segment.bytes().peekable();
+                prev[0] = line as i64;
+                for field in &mut prev[1..] {
+                    *field += decode_vlq(&mut chars);
+                }
+                assert!(chars.peek().is_none());
+                decoded.push(prev);
+            }
+        }
+        decoded
+    }
+
+    #[test]
+    fn vlq() {
+        let encode = |value| {
+            let mut out = String::new();
+            encode_vlq(value, &mut out);
+            out
+        };
+        assert_eq!(encode(0), "A");
+        assert_eq!(encode(1), "C");
+        assert_eq!(encode(-1), "D");
+        assert_eq!(encode(15), "e");
+        assert_eq!(encode(16), "gB");
+        assert_eq!(encode(-16), "hB");
+        assert_eq!(encode(1000), "w+B");
+        for value in [0, 1, -1, 15, 16, -16, 1000, -123_456] {
+            assert_eq!(decode_vlq(&mut encode(value).bytes()), value);
+        }
+    }
+
+    #[test]
+    fn mappings_are_relative() {
+        let css = "a{}\nb{}c{}";
+        let position = |source, line, column| SourcePosition {
+            source,
+            line,
+            column,
+        };
+        let map = source_map(
+            "out.css",
+            css,
+            &[String::from("a.jsx"), String::from("b.jsx")],
+            &[
+                (0, position(0, 3, 10)),
+                (4, position(1, 0, 2)),
+                (7, position(0, 3, 20)),
+            ],
+        );
+        assert_eq!(
+            map,
+            r#"{"version":3,"file":"out.css","sources":["a.jsx","b.jsx"],"names":[],"mappings":"AAGU;ACHR,GDGkB"}"#
+        );
+        assert_eq!(
+            decode_mappings("AAGU;ACHR,GDGkB"),
+            [[0, 0, 0, 3, 10], [1, 0, 1, 0, 2], [1, 3, 0, 3, 20]]
+        );
+    }
+
+    #[test]
+    fn compiler_maps_rules_to_utils() {
+        let mut compiler = Compiler::new();
+        let app = SourceOrigin {
+            file: String::from("src/App.jsx"),
+            line: 9,
+            column: 14,
+        };
+        let button = SourceOrigin {
+            file: String::from("src/Button.jsx"),
+            line: 2,
+            column: 5,
+        };
+        compiler
+            .generate_classes_at("p=1 @md:m=2\n  hover:bg-c=red-500", &app)
+            .unwrap();
+        compiler
+            .generate_classes_at("m=[0.25rem] bg-c=[#ü]", &button)
+            .unwrap();
+        compiler.generate_classes("m=3").unwrap();
+        let (css, map) = compiler.get_css_with_source_map("out.css");
+        assert_eq!(
+            css,
+            concat!(
+                "._E0{padding:0.25rem;}._E3{margin:0.25rem;}._E4{background-color:#ü;}",
+                "._E5{margin:0.75rem;}._E2:hover{background-color:#ef4444;}",
+                "@media (min-width:768px){._E1{margin:0.5rem;}}",
+            )
+        );
+        let column = |rule: &str| utf16_len(&css[..css.find(rule).unwrap()]) as i64;
+        let mappings = map
+            .split(r#""mappings":""#)
+            .nth(1)
+            .unwrap()
+            .trim_end_matches("\"}");
+        // Rules point at their util, after the variants. `m=3` has no origin
+        assert_eq!(
+            decode_mappings(mappings),
+            [
+                [0, column("._E0"), 0, 9, 14],
+                [0, column("._E3"), 1, 2, 5],
+                [0, column("._E4"), 1, 2, 17],
+                [0, column("._E2"), 0, 10, 8],
+                [0, column("._E1"), 0, 9, 22],
+            ]
+        );
+    }
+}
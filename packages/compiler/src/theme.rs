@@ -0,0 +1,407 @@
+use std::{collections::BTreeMap, fmt, fs, io, path::Path};
+
+use serde::Deserialize;
+
+use crate::utils::{
+    colors::COLORS,
+    media_queries::BREAKPOINTS,
+    values::{BORDER_RADII, FONT_FAMILIES, FONT_SIZES, MAX_WIDTHS, SPACING},
+};
+
+/// Named values of a scale, in order
+pub type Scale<T = String> = Vec<(String, T)>;
+
+/// The design tokens utils and media queries resolve named values with. The
+/// default is the built-in preset, close to Tailwind's.
+///
+/// A theme file replaces a scale by setting it and extends it under `extend`:
+///
+/// ```toml
+/// spacing_step = 0.5
+///
+/// [breakpoints]
+/// tablet = "600px"
+/// desktop = "1200px"
+///
+/// [extend.colors]
+/// brand = "#ff5a1f"
+///
+/// [extend.font_sizes]
+/// huge = ["10rem", "1"]
+///
+/// [shortcuts]
+/// card = "p=6 border-r=lg bg-c=white @dark:bg-c=gray-800"
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    /// `bg-c`, `text-c` and `border-c`
+    pub colors: Scale,
+
+    /// Named spacing values, tried before `spacing_step`
+    pub spacing: Scale,
+
+    /// rem per step of numeric spacing, `p=4` is `1rem` with `0.25`. `None`
+    /// only allows the named values
+    pub spacing_step: Option<f32>,
+
+    /// Min width of every breakpoint, `md = "768px"` gives `@md`
+    pub breakpoints: Scale,
+
+    /// `font-f`
+    pub fonts: Scale,
+
+    /// `font-s`
+    pub font_sizes: Scale<FontSize>,
+
+    /// `border-r`
+    pub radii: Scale,
+
+    /// `max-w`
+    pub max_widths: Scale,
+
+    /// Utils written as ecss, `card = "p=6 border-r=lg"` makes `hover:card`
+    /// the same as `hover:(p=6 border-r=lg)`
+    pub shortcuts: Scale,
+}
+
+/// A font size, with the line height that goes with it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FontSize {
+    pub size: String,
+    pub line_height: Option<String>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            colors: to_scale(COLORS),
+            spacing: to_scale(SPACING),
+            spacing_step: Some(0.25),
+            breakpoints: to_scale(BREAKPOINTS),
+            fonts: to_scale(FONT_FAMILIES),
+            font_sizes: FONT_SIZES
+                .iter()
+                .map(|(name, size, line_height)| {
+                    (
+                        name.to_string(),
+                        FontSize {
+                            size: size.to_string(),
+                            line_height: Some(line_height.to_string()),
+                        },
+                    )
+                })
+                .collect(),
+            radii: to_scale(BORDER_RADII),
+            max_widths: to_scale(MAX_WIDTHS),
+            shortcuts: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ThemeError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThemeError::Io(err) => write!(f, "Couldn't access theme: {}", err),
+            ThemeError::Toml(err) => write!(f, "Invalid theme: {}", err),
+            ThemeError::Json(err) => write!(f, "Invalid theme: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+impl From<io::Error> for ThemeError {
+    fn from(err: io::Error) -> Self {
+        ThemeError::Io(err)
+    }
+}
+
+impl From<toml::de::Error> for ThemeError {
+    fn from(err: toml::de::Error) -> Self {
+        ThemeError::Toml(err)
+    }
+}
+
+impl From<serde_json::Error> for ThemeError {
+    fn from(err: serde_json::Error) -> Self {
+        ThemeError::Json(err)
+    }
+}
+
+impl Theme {
+    pub fn from_toml(toml: &str) -> Result<Self, ThemeError> {
+        Ok(toml::from_str::<ThemeFile>(toml)?.into_theme())
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, ThemeError> {
+        Ok(serde_json::from_str::<ThemeFile>(json)?.into_theme())
+    }
+
+    /// JSON if `path` ends with `.json`, TOML otherwise
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ThemeError> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+        match path.extension() {
+            Some(extension) if extension == "json" => Self::from_json(&contents),
+            _ => Self::from_toml(&contents),
+        }
+    }
+}
+
+/// Looks up `name` in `scale`
+pub(crate) fn lookup<'a, T>(scale: &'a [(String, T)], name: &str) -> Option<&'a T> {
+    scale
+        .iter()
+        .find(|(scale_name, _)| scale_name == name)
+        .map(|(_, value)| value)
+}
+
+fn to_scale(values: &[(&str, &str)]) -> Scale {
+    values
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+/// Replaces the values of known names in place and adds the rest
+fn extend<T>(scale: &mut Scale<T>, values: BTreeMap<String, T>) {
+    for (name, value) in values {
+        match scale.iter_mut().find(|(scale_name, _)| *scale_name == name) {
+            Some((_, old_value)) => *old_value = value,
+            None => scale.push((name, value)),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ThemeFile {
+    colors: Option<BTreeMap<String, String>>,
+    spacing: Option<BTreeMap<String, String>>,
+    spacing_step: Option<f32>,
+    breakpoints: Option<BTreeMap<String, String>>,
+    fonts: Option<BTreeMap<String, String>>,
+    font_sizes: Option<BTreeMap<String, FontSizeFile>>,
+    radii: Option<BTreeMap<String, String>>,
+    max_widths: Option<BTreeMap<String, String>>,
+    shortcuts: Option<BTreeMap<String, String>>,
+    extend: ExtendFile,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ExtendFile {
+    colors: BTreeMap<String, String>,
+    spacing: BTreeMap<String, String>,
+    breakpoints: BTreeMap<String, String>,
+    fonts: BTreeMap<String, String>,
+    font_sizes: BTreeMap<String, FontSizeFile>,
+    radii: BTreeMap<String, String>,
+    max_widths: BTreeMap<String, String>,
+    shortcuts: BTreeMap<String, String>,
+}
+
+/// `"1rem"` or `["1rem", "1.5rem"]` with a line height
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum FontSizeFile {
+    Size(String),
+    WithLineHeight(String, String),
+}
+
+impl From<FontSizeFile> for FontSize {
+    fn from(file: FontSizeFile) -> Self {
+        match file {
+            FontSizeFile::Size(size) => FontSize {
+                size,
+                line_height: None,
+            },
+            FontSizeFile::WithLineHeight(size, line_height) => FontSize {
+                size,
+                line_height: Some(line_height),
+            },
+        }
+    }
+}
+
+impl ThemeFile {
+    fn into_theme(self) -> Theme {
+        let mut theme = Theme::default();
+
+        // Replacing the spacing scale also drops numeric spacing, unless a
+        // step is given
+        if self.spacing.is_some() {
+            theme.spacing_step = None;
+        }
+        if self.spacing_step.is_some() {
+            theme.spacing_step = self.spacing_step;
+        }
+
+        replace(&mut theme.colors, self.colors);
+        replace(&mut theme.spacing, self.spacing);
+        replace(&mut theme.breakpoints, self.breakpoints);
+        replace(&mut theme.fonts, self.fonts);
+        replace(
+            &mut theme.font_sizes,
+            self.font_sizes.map(|font_sizes| {
+                font_sizes
+                    .into_iter()
+                    .map(|(name, font_size)| (name, font_size.into()))
+                    .collect()
+            }),
+        );
+        replace(&mut theme.radii, self.radii);
+        replace(&mut theme.max_widths, self.max_widths);
+        replace(&mut theme.shortcuts, self.shortcuts);
+
+        let extension = self.extend;
+        extend(&mut theme.colors, extension.colors);
+        extend(&mut theme.spacing, extension.spacing);
+        extend(&mut theme.breakpoints, extension.breakpoints);
+        extend(&mut theme.fonts, extension.fonts);
+        extend(
+            &mut theme.font_sizes,
+            extension
+                .font_sizes
+                .into_iter()
+                .map(|(name, font_size)| (name, font_size.into()))
+                .collect(),
+        );
+        extend(&mut theme.radii, extension.radii);
+        extend(&mut theme.max_widths, extension.max_widths);
+        extend(&mut theme.shortcuts, extension.shortcuts);
+
+        theme
+    }
+}
+
+fn replace<T>(scale: &mut Scale<T>, values: Option<BTreeMap<String, T>>) {
+    if let Some(values) = values {
+        *scale = values.into_iter().collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{Compiler, CompilerOptions};
+
+    fn names<T>(scale: &Scale<T>) -> Vec<&str> {
+        scale.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    #[test]
+    fn setting_a_scale_replaces_it() {
+        let theme = Theme::from_toml("[colors]\nbrand = \"#ff5a1f\"\nink = \"#111\"").unwrap();
+        assert_eq!(names(&theme.colors), ["brand", "ink"]);
+        assert_eq!(
+            lookup(&theme.colors, "brand"),
+            Some(&String::from("#ff5a1f"))
+        );
+        assert_eq!(lookup(&theme.colors, "blue-500"), None);
+        assert_eq!(theme.spacing, Theme::default().spacing);
+    }
+
+    #[test]
+    fn extend_keeps_the_defaults() {
+        let theme = Theme::from_toml(
+            "[extend.colors]\nbrand = \"#ff5a1f\"\nblue-500 = \"#00f\"\n[extend.font_sizes]\nhuge = [\"10rem\", \"1\"]\ntiny = \"0.5rem\"",
+        )
+        .unwrap();
+        let default = Theme::default();
+        assert_eq!(theme.colors.len(), default.colors.len() + 1);
+        assert_eq!(
+            theme.colors.last(),
+            Some(&(String::from("brand"), String::from("#ff5a1f")))
+        );
+        // Known names keep their place
+        let index = |scale: &Scale| scale.iter().position(|(name, _)| name == "blue-500");
+        assert_eq!(index(&theme.colors), index(&default.colors));
+        assert_eq!(
+            lookup(&theme.colors, "blue-500"),
+            Some(&String::from("#00f"))
+        );
+        assert_eq!(
+            lookup(&theme.font_sizes, "huge"),
+            Some(&FontSize {
+                size: String::from("10rem"),
+                line_height: Some(String::from("1")),
+            })
+        );
+        assert_eq!(
+            lookup(&theme.font_sizes, "tiny"),
+            Some(&FontSize {
+                size: String::from("0.5rem"),
+                line_height: None,
+            })
+        );
+    }
+
+    #[test]
+    fn spacing_step() {
+        assert_eq!(Theme::default().spacing_step, Some(0.25));
+        assert_eq!(
+            Theme::from_toml("spacing_step = 0.5").unwrap().spacing_step,
+            Some(0.5)
+        );
+        // Replacing the named spacing drops the step unless one is given
+        let theme = Theme::from_toml("[spacing]\ngutter = \"1.5rem\"").unwrap();
+        assert_eq!(names(&theme.spacing), ["gutter"]);
+        assert_eq!(theme.spacing_step, None);
+        let theme = Theme::from_toml("spacing_step = 0.5\n[spacing]\ngutter = \"1.5rem\"").unwrap();
+        assert_eq!(theme.spacing_step, Some(0.5));
+        let theme = Theme::from_toml("[extend.spacing]\ngutter = \"1.5rem\"").unwrap();
+        assert_eq!(theme.spacing_step, Some(0.25));
+    }
+
+    #[test]
+    fn json_is_read_like_toml() {
+        let json = Theme::from_json(
+            r##"{"breakpoints": {"tablet": "600px"}, "extend": {"colors": {"brand": "#ff5a1f"}}}"##,
+        )
+        .unwrap();
+        let toml = Theme::from_toml(
+            "[breakpoints]\ntablet = \"600px\"\n[extend.colors]\nbrand = \"#ff5a1f\"",
+        )
+        .unwrap();
+        assert_eq!(json, toml);
+        assert_eq!(names(&json.breakpoints), ["tablet"]);
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        let err = Theme::from_toml("colours = 1").unwrap_err().to_string();
+        assert!(err.starts_with("Invalid theme: TOML parse error at line 1, column 1"));
+        assert!(err.contains("unknown field `colours`, expected one of `colors`"));
+
+        let err = Theme::from_json(r#"{"extend": {"colour": {}}}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("Invalid theme: unknown field `colour`, expected one of `colors`"));
+    }
+
+    #[test]
+    fn utils_use_the_theme() {
+        let theme = Theme::from_toml(
+            "spacing_step = 0.5\n[breakpoints]\ntablet = \"600px\"\n[extend.colors]\nbrand = \"#ff5a1f\"",
+        )
+        .unwrap();
+        let mut compiler = Compiler::with_options(CompilerOptions {
+            theme,
+            ..Default::default()
+        });
+        compiler.generate_classes("@tablet:p=2 bg-c=brand").unwrap();
+        assert_eq!(
+            compiler.get_css(),
+            "._E1{background-color:#ff5a1f;}@media (min-width:600px){._E0{padding:1rem;}}"
+        );
+        assert!(compiler.generate_classes("@md:p=1").is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use std::fmt;
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum Token {
     Whitespace,
@@ -24,3 +26,59 @@ pub enum Token {
 
     RawValue(String),
 }
+
+/// Token without its content, used to describe what the parser expected
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TokenKind {
+    Whitespace,
+    Colon,
+    LParen,
+    RParen,
+    Eq,
+    At,
+    Underscore,
+    Word,
+    RawValue,
+}
+
+impl Token {
+    pub fn kind(&self) -> TokenKind {
+        match self {
+            Token::Whitespace => TokenKind::Whitespace,
+            Token::Colon => TokenKind::Colon,
+            Token::LParen => TokenKind::LParen,
+            Token::RParen => TokenKind::RParen,
+            Token::Eq => TokenKind::Eq,
+            Token::At => TokenKind::At,
+            Token::Underscore => TokenKind::Underscore,
+            Token::Word(_) => TokenKind::Word,
+            Token::RawValue(_) => TokenKind::RawValue,
+        }
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "`{}`", word),
+            Token::RawValue(raw) => write!(f, "`[{}]`", raw),
+            token => token.kind().fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            TokenKind::Whitespace => "whitespace",
+            TokenKind::Colon => "`:`",
+            TokenKind::LParen => "`(`",
+            TokenKind::RParen => "`)`",
+            TokenKind::Eq => "`=`",
+            TokenKind::At => "`@`",
+            TokenKind::Underscore => "`_`",
+            TokenKind::Word => "word",
+            TokenKind::RawValue => "raw value",
+        })
+    }
+}
@@ -1,18 +1,249 @@
-use anyhow::{bail, Result};
-
-pub fn get_media_query(name: &str) -> Result<String> {
-    Ok(match name {
-        "sm" => String::from("(min-width:640px)"),
-        "md" => String::from("(min-width:768px)"),
-        "lg" => String::from("(min-width:1024px)"),
-        "xl" => String::from("(min-width:1280px)"),
-        "2xl" => String::from("(min-width:1536px)"),
-        "dark" => String::from("(prefers-color-scheme: dark)"),
-        "portrait" => String::from("(orientation: portrait)"),
-        "landscape" => String::from("(orientation: landscape)"),
-        "motion-safe" => String::from("(prefers-reduced-motion: no-preference)"),
-        "motion-reduce" => String::from("(prefers-reduced-motion: reduce)"),
-        "print" => String::from("print"),
-        _ => bail!("Unknown media query: {}", name),
+use crate::{
+    theme::{lookup, Theme},
+    utils::{suggest, UtilError},
+};
+
+/// Breakpoints of the default theme
+pub const BREAKPOINTS: &[(&str, &str)] = &[
+    ("sm", "640px"),
+    ("md", "768px"),
+    ("lg", "1024px"),
+    ("xl", "1280px"),
+    ("2xl", "1536px"),
+];
+
+pub const MEDIA_QUERIES: &[(&str, &str)] = &[
+    ("dark", "(prefers-color-scheme: dark)"),
+    ("portrait", "(orientation: portrait)"),
+    ("landscape", "(orientation: landscape)"),
+    ("motion-safe", "(prefers-reduced-motion: no-preference)"),
+    ("motion-reduce", "(prefers-reduced-motion: reduce)"),
+    ("print", "print"),
+];
+
+/// Breakpoints are `(min-width:768px)` for `md`, `(max-width:767.98px)` for
+/// `max-md` and `(min-width:768px) and (max-width:1023.98px)` for the range
+/// `md-lg`
+pub fn get_media_query(name: &str, theme: &Theme) -> Result<String, UtilError> {
+    if let Some(width) = lookup(&theme.breakpoints, name) {
+        return Ok(format!("(min-width:{})", width));
+    }
+    if let Some(width) = name
+        .strip_prefix("max-")
+        .and_then(|breakpoint| lookup(&theme.breakpoints, breakpoint))
+    {
+        return Ok(format!("(max-width:{})", below(width)));
+    }
+    if let Some(((from, min), (to, max))) = breakpoint_range(name, theme) {
+        if let (Some(min_px), Some(max_px)) = (length(min), length(max)) {
+            if min_px >= max_px {
+                return Err(UtilError::EmptyBreakpointRange {
+                    name: name.to_string(),
+                    from: from.to_string(),
+                    to: to.to_string(),
+                });
+            }
+        }
+        return Ok(format!(
+            "(min-width:{}) and (max-width:{})",
+            min,
+            below(max)
+        ));
+    }
+    match MEDIA_QUERIES.iter().find(|(n, _)| *n == name) {
+        Some((_, css)) => Ok(String::from(*css)),
+        None => {
+            let max_breakpoints: Vec<String> = theme
+                .breakpoints
+                .iter()
+                .map(|(n, _)| format!("max-{}", n))
+                .collect();
+            let mut suggestions = suggest(
+                name,
+                theme
+                    .breakpoints
+                    .iter()
+                    .map(|(n, _)| n.as_str())
+                    .chain(max_breakpoints.iter().map(String::as_str))
+                    .chain(MEDIA_QUERIES.iter().map(|(n, _)| *n)),
+            );
+            if suggestions.is_empty() {
+                suggestions = replaced_breakpoint(name, theme);
+            }
+            Err(UtilError::UnknownMediaQuery {
+                name: name.to_string(),
+                suggestions,
+            })
+        }
+    }
+}
+
+/// The theme's breakpoints when `name` is a default one the theme replaced,
+/// `tablet` and `desktop` for `md`, `max-tablet` and `max-desktop` for `max-md`
+fn replaced_breakpoint(name: &str, theme: &Theme) -> Vec<String> {
+    let (prefix, breakpoint) = match name.strip_prefix("max-") {
+        Some(breakpoint) => ("max-", breakpoint),
+        None => ("", name),
+    };
+    if !BREAKPOINTS.iter().any(|(n, _)| *n == breakpoint) {
+        return Vec::new();
+    }
+    theme
+        .breakpoints
+        .iter()
+        .map(|(n, _)| format!("{}{}", prefix, n))
+        .collect()
+}
+
+/// Name and width of a breakpoint
+type Breakpoint<'a, 'b> = (&'a str, &'b String);
+
+/// The breakpoints in `md-lg`
+fn breakpoint_range<'a, 'b>(
+    name: &'a str,
+    theme: &'b Theme,
+) -> Option<(Breakpoint<'a, 'b>, Breakpoint<'a, 'b>)> {
+    name.match_indices('-').find_map(|(index, _)| {
+        let (from, to) = (&name[..index], &name[index + 1..]);
+        Some((
+            (from, lookup(&theme.breakpoints, from)?),
+            (to, lookup(&theme.breakpoints, to)?),
+        ))
     })
 }
+
+/// A width just below `width`, where its `min-width` stops applying. The
+/// `(width<768px)` range syntax would be exact but needs Safari 16.4
+fn below(width: &str) -> String {
+    let width = width.trim();
+    let number_len = width
+        .find(|char: char| !char.is_ascii_digit() && char != '.')
+        .unwrap_or(width.len());
+    let step = match &width[number_len..] {
+        "px" => 0.02,
+        "em" | "rem" => 0.00125,
+        _ => return format!("calc({} - 0.02px)", width),
+    };
+    match width[..number_len].parse::<f64>() {
+        Ok(number) => format!(
+            "{}{}",
+            ((number - step) * 100_000.0).round() / 100_000.0,
+            &width[number_len..]
+        ),
+        Err(_) => format!("calc({} - 0.02px)", width),
+    }
+}
+
+/// `min-width` of a media query in px, `(min-width: 40em)` is `640`
+pub fn min_width(media_query: &str) -> Option<f64> {
+    let (_, rest) = media_query.split_once("min-width")?;
+    length(rest.trim_start().strip_prefix(':')?)
+}
+
+/// Width a media query stays below in px, from `(max-width: 40em)` or
+/// `(width < 640px)`
+pub fn max_width(media_query: &str) -> Option<f64> {
+    if let Some((_, rest)) = media_query.split_once("max-width") {
+        return length(rest.trim_start().strip_prefix(':')?);
+    }
+    media_query.match_indices("width").find_map(|(index, _)| {
+        let rest = media_query[index + "width".len()..]
+            .trim_start()
+            .strip_prefix('<')?;
+        length(rest.strip_prefix('=').unwrap_or(rest))
+    })
+}
+
+/// A length at the start of `str` in px, em and rem being 16px
+fn length(str: &str) -> Option<f64> {
+    let str = str.trim_start();
+    let number_len = str
+        .find(|char: char| !char.is_ascii_digit() && char != '.')
+        .unwrap_or(str.len());
+    let number: f64 = str[..number_len].parse().ok()?;
+    match str[number_len..].trim_start() {
+        unit if unit.starts_with("px") => Some(number),
+        unit if unit.starts_with("em") || unit.starts_with("rem") => Some(number * 16.0),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_breakpoints() {
+        let theme =
+            Theme::from_toml("[breakpoints]\nmd = \"768px\"\nwide = \"80em\"\nhalf = \"50vw\"")
+                .unwrap();
+        assert_eq!(
+            get_media_query("max-md", &theme),
+            Ok(String::from("(max-width:767.98px)"))
+        );
+        assert_eq!(
+            get_media_query("max-wide", &theme),
+            Ok(String::from("(max-width:79.99875em)"))
+        );
+        assert_eq!(
+            get_media_query("max-half", &theme),
+            Ok(String::from("(max-width:calc(50vw - 0.02px))"))
+        );
+        assert_eq!(max_width("(max-width:79.99875em)"), Some(1279.98));
+    }
+
+    #[test]
+    fn breakpoint_ranges() {
+        let theme = Theme::default();
+        assert_eq!(
+            get_media_query("md-lg", &theme),
+            Ok(String::from("(min-width:768px) and (max-width:1023.98px)"))
+        );
+        assert_eq!(
+            get_media_query("lg-md", &theme),
+            Err(UtilError::EmptyBreakpointRange {
+                name: String::from("lg-md"),
+                from: String::from("lg"),
+                to: String::from("md"),
+            })
+        );
+        assert!(matches!(
+            get_media_query("md-md", &theme),
+            Err(UtilError::EmptyBreakpointRange { .. })
+        ));
+    }
+
+    #[test]
+    fn suggests_breakpoints_of_theme() {
+        let theme =
+            Theme::from_toml("[breakpoints]\ntablet = \"40em\"\ndesktop = \"1200px\"").unwrap();
+        let suggestions = |name| {
+            get_media_query(name, &theme)
+                .unwrap_err()
+                .suggestions()
+                .to_vec()
+        };
+        assert_eq!(suggestions("md"), ["desktop", "tablet"]);
+        assert_eq!(suggestions("max-md"), ["max-desktop", "max-tablet"]);
+        assert_eq!(suggestions("tablett"), ["tablet"]);
+        assert!(suggestions("medium").is_empty());
+    }
+
+    #[test]
+    fn breakpoint_ranges_of_theme() {
+        let theme =
+            Theme::from_toml("[breakpoints]\ntablet = \"40em\"\ndesktop = \"1200px\"").unwrap();
+        assert_eq!(
+            get_media_query("tablet-desktop", &theme),
+            Ok(String::from("(min-width:40em) and (max-width:1199.98px)"))
+        );
+        assert_eq!(
+            get_media_query("desktop-tablet", &theme),
+            Err(UtilError::EmptyBreakpointRange {
+                name: String::from("desktop-tablet"),
+                from: String::from("desktop"),
+                to: String::from("tablet"),
+            })
+        );
+    }
+}
@@ -0,0 +1,639 @@
+use std::{borrow::Cow, fmt, sync::Arc};
+
+use crate::{
+    ast::Value,
+    theme::Theme,
+    utils::{colors::get_color_value, suggest, values::*, UtilError},
+};
+
+/// Resolves a named or raw value to CSS, `get_spacing_value` turns `4` into `1rem`
+pub type ValueFn = fn(&Value, &Theme) -> anyhow::Result<String>;
+
+/// Names a value fn accepts, for suggestions
+pub type KnownValuesFn = fn(&Theme) -> Vec<&str>;
+
+/// A value fn with its name for errors
+#[derive(Debug, Clone, Copy)]
+pub struct NamedValueFn {
+    pub name: &'static str,
+    pub value_fn: ValueFn,
+
+    /// `None` if it doesn't take names, or too many to suggest
+    pub known_values: Option<KnownValuesFn>,
+}
+
+impl NamedValueFn {
+    /// A value fn without names to suggest
+    pub fn new(name: &'static str, value_fn: ValueFn) -> Self {
+        Self {
+            name,
+            value_fn,
+            known_values: None,
+        }
+    }
+}
+
+/// Tried in order
+pub type ValueFns = Vec<NamedValueFn>;
+
+/// `value_fns![get_spacing_value, get_auto_value]`
+macro_rules! value_fns {
+    ($($value_fn:ident),+ $(,)?) => {
+        vec![$(named_value_fn!($value_fn)),+]
+    };
+}
+
+/// Value fns resolving names from the theme know the names of their scale
+macro_rules! named_value_fn {
+    (get_color_value) => {
+        named_value_fn!(get_color_value, colors)
+    };
+    (get_spacing_value) => {
+        named_value_fn!(get_spacing_value, spacing)
+    };
+    (get_max_width_value) => {
+        named_value_fn!(get_max_width_value, max_widths)
+    };
+    (get_font_family_value) => {
+        named_value_fn!(get_font_family_value, fonts)
+    };
+    (get_font_size_value) => {
+        named_value_fn!(get_font_size_value, font_sizes)
+    };
+    (get_border_radius_value) => {
+        named_value_fn!(get_border_radius_value, radii)
+    };
+    ($value_fn:ident, $scale:ident) => {
+        NamedValueFn {
+            name: stringify!($value_fn),
+            value_fn: $value_fn,
+            known_values: Some(|theme| names(&theme.$scale)),
+        }
+    };
+    ($value_fn:ident) => {
+        NamedValueFn {
+            name: stringify!($value_fn),
+            value_fn: $value_fn,
+            known_values: None,
+        }
+    };
+}
+
+fn names<T>(scale: &[(String, T)]) -> Vec<&str> {
+    scale.iter().map(|(name, _)| name.as_str()).collect()
+}
+
+/// A util and the declarations it sets
+#[derive(Debug, Clone)]
+pub struct Utility {
+    pub name: Cow<'static, str>,
+    pub declarations: Vec<Declaration>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Declaration {
+    pub property: &'static str,
+    pub value: DeclarationValue,
+}
+
+#[derive(Debug, Clone)]
+pub enum DeclarationValue {
+    /// Always this value
+    Fixed(&'static str),
+
+    /// The util's value, resolved by the first value fn that accepts it
+    Value(ValueFns),
+
+    /// Like `Value`, but left out if no value fn accepts the util's value
+    OptionalValue(ValueFns),
+}
+
+impl Utility {
+    /// A util without a value, `block` sets `display:block;`
+    pub fn fixed(
+        name: impl Into<Cow<'static, str>>,
+        declarations: &[(&'static str, &'static str)],
+    ) -> Self {
+        Self {
+            name: name.into(),
+            declarations: declarations
+                .iter()
+                .map(|(property, value)| Declaration {
+                    property,
+                    value: DeclarationValue::Fixed(value),
+                })
+                .collect(),
+        }
+    }
+
+    /// A util setting every property to its value, `p-x=4` sets
+    /// `padding-left` and `padding-right`
+    ///
+    /// ```
+    /// use emergentcss::{
+    ///     compiler::*,
+    ///     value_fns::{get_auto_value, get_spacing_value},
+    /// };
+    ///
+    /// let mut utilities = UtilityRegistry::default();
+    /// utilities.register(Utility::valued(
+    ///     String::from("inset-x"),
+    ///     &["left", "right"],
+    ///     vec![
+    ///         NamedValueFn::new("get_auto_value", get_auto_value),
+    ///         NamedValueFn::new("get_spacing_value", get_spacing_value),
+    ///     ],
+    /// ));
+    /// let mut compiler = Compiler::with_options(CompilerOptions {
+    ///     utilities,
+    ///     ..Default::default()
+    /// });
+    ///
+    /// compiler.generate_classes("inset-x=4 inset-x=auto").unwrap();
+    /// assert_eq!(
+    ///     compiler.get_css(),
+    ///     "._E0{left:1rem;right:1rem;}._E1{left:auto;right:auto;}"
+    /// );
+    /// assert!(compiler.generate_classes("inset-x=wide").is_err());
+    /// ```
+    pub fn valued(
+        name: impl Into<Cow<'static, str>>,
+        properties: &[&'static str],
+        value_fns: ValueFns,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            declarations: properties
+                .iter()
+                .map(|property| Declaration {
+                    property,
+                    value: DeclarationValue::Value(value_fns.clone()),
+                })
+                .collect(),
+        }
+    }
+
+    /// Adds a declaration
+    pub fn with(mut self, property: &'static str, value: DeclarationValue) -> Self {
+        self.declarations.push(Declaration { property, value });
+        self
+    }
+
+    pub fn takes_value(&self) -> bool {
+        self.declarations
+            .iter()
+            .any(|declaration| !matches!(declaration.value, DeclarationValue::Fixed(_)))
+    }
+
+    /// The CSS properties it sets, in order
+    pub fn properties(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.declarations
+            .iter()
+            .map(|declaration| declaration.property)
+    }
+
+    /// Names of the value fns its value can be resolved by
+    pub fn value_fn_names(&self) -> Vec<&'static str> {
+        let mut names = Vec::new();
+        for declaration in &self.declarations {
+            if let DeclarationValue::Value(value_fns) | DeclarationValue::OptionalValue(value_fns) =
+                &declaration.value
+            {
+                for value_fn in value_fns {
+                    if !names.contains(&value_fn.name) {
+                        names.push(value_fn.name);
+                    }
+                }
+            }
+        }
+        names
+    }
+
+    /// `value` is `None` for utils without a value. The error holds the value
+    /// fns that were tried
+    fn css(&self, value: Option<&Value>, theme: &Theme) -> Result<String, ValueFns> {
+        let mut css = String::new();
+        for declaration in &self.declarations {
+            let declared = match (&declaration.value, value) {
+                (DeclarationValue::Fixed(fixed), _) => fixed.to_string(),
+                (DeclarationValue::Value(value_fns), Some(value)) => {
+                    try_value_fns(value_fns, value, theme)?
+                }
+                (DeclarationValue::OptionalValue(value_fns), Some(value)) => {
+                    match try_value_fns(value_fns, value, theme) {
+                        Ok(declared) => declared,
+                        Err(_) => continue,
+                    }
+                }
+                (_, None) => continue,
+            };
+            css.push_str(&format!("{}:{};", declaration.property, declared));
+        }
+        Ok(css)
+    }
+}
+
+fn try_value_fns(
+    value_fns: &[NamedValueFn],
+    value: &Value,
+    theme: &Theme,
+) -> Result<String, ValueFns> {
+    for NamedValueFn { value_fn, .. } in value_fns {
+        if let Ok(val) = value_fn(value, theme) {
+            return Ok(val);
+        }
+    }
+
+    Err(value_fns.to_vec())
+}
+
+/// Utils written in Rust, for utils that don't fit `Utility`. Consulted for
+/// utils that aren't registered, in the order the plugins were registered.
+///
+/// ```
+/// use emergentcss::{compiler::*, error::ErrorCode, theme::Theme};
+///
+/// struct Elevation;
+///
+/// impl UtilityPlugin for Elevation {
+///     fn names(&self) -> &[&'static str] {
+///         &["elevation"]
+///     }
+///
+///     fn declarations(
+///         &self,
+///         property: &str,
+///         value: Option<&Value>,
+///         _: &Theme,
+///     ) -> Option<Result<Vec<(String, String)>, String>> {
+///         match (property, value) {
+///             ("elevation", Some(Value::Iden(level))) => Some(match level.parse::<u8>() {
+///                 Ok(level @ 0..=5) => Ok(vec![(
+///                     String::from("box-shadow"),
+///                     format!("0 {}px {}px rgba(0,0,0,.2)", level, level * 2),
+///                 )]),
+///                 _ => Err(String::from("expected a level from 0 to 5")),
+///             }),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let mut utilities = UtilityRegistry::default();
+/// utilities.register_plugin(Elevation);
+/// let mut compiler = Compiler::with_options(CompilerOptions {
+///     utilities,
+///     ..Default::default()
+/// });
+///
+/// assert_eq!(compiler.generate_classes("elevation=3").unwrap(), ["_E0"]);
+/// assert_eq!(compiler.get_css(), "._E0{box-shadow:0 3px 6px rgba(0,0,0,.2);}");
+/// assert_eq!(
+///     compiler.generate_classes("elevation=9").unwrap_err()[0].code(),
+///     ErrorCode::RejectedByPlugin
+/// );
+/// ```
+pub trait UtilityPlugin: Send + Sync {
+    /// Names of its utils, for completion and suggestions
+    fn names(&self) -> &[&'static str] {
+        &[]
+    }
+
+    /// Properties and values `property` sets, with `value` if it has one.
+    /// `None` if it isn't one of the plugin's utils, an error message if it is
+    /// but can't be used like this
+    fn declarations(
+        &self,
+        property: &str,
+        value: Option<&Value>,
+        theme: &Theme,
+    ) -> Option<Result<Vec<(String, String)>, String>>;
+}
+
+/// All utils that can be used. A name can be registered twice, once with and
+/// once without a value, like `flex` and `flex=1`
+#[derive(Clone)]
+pub struct UtilityRegistry {
+    utilities: Vec<Utility>,
+    plugins: Vec<Arc<dyn UtilityPlugin>>,
+}
+
+impl fmt::Debug for UtilityRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("UtilityRegistry")
+            .field("utilities", &self.utilities)
+            .field("plugins", &self.plugins.len())
+            .finish()
+    }
+}
+
+impl Default for UtilityRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl UtilityRegistry {
+    /// A registry without any utils
+    pub fn empty() -> Self {
+        Self {
+            utilities: Vec::new(),
+            plugins: Vec::new(),
+        }
+    }
+
+    /// Replaces the util with the same name that also does or doesn't take a
+    /// value
+    pub fn register(&mut self, utility: Utility) {
+        match self.utilities.iter_mut().find(|registered| {
+            registered.name == utility.name && registered.takes_value() == utility.takes_value()
+        }) {
+            Some(registered) => *registered = utility,
+            None => self.utilities.push(utility),
+        }
+    }
+
+    pub fn register_plugin(&mut self, plugin: impl UtilityPlugin + 'static) {
+        self.plugins.push(Arc::new(plugin));
+    }
+
+    pub fn get(&self, name: &str, takes_value: bool) -> Option<&Utility> {
+        self.utilities
+            .iter()
+            .find(|utility| utility.name == name && utility.takes_value() == takes_value)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.utilities.iter().any(|utility| utility.name == name)
+    }
+
+    /// In order of registration, without the plugins
+    pub fn iter(&self) -> impl Iterator<Item = &Utility> {
+        self.utilities.iter()
+    }
+
+    /// Names of the utils and plugin utils starting with `prefix`, without
+    /// duplicates
+    pub fn complete(&self, prefix: &str) -> Vec<&str> {
+        let mut names = Vec::new();
+        for name in self.names() {
+            if name.starts_with(prefix) && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    fn names(&self) -> impl Iterator<Item = &str> {
+        self.utilities.iter().map(|utility| &*utility.name).chain(
+            self.plugins
+                .iter()
+                .flat_map(|plugin| plugin.names().iter().copied()),
+        )
+    }
+
+    /// Declarations of `property`, with `value` if it has one
+    pub(crate) fn util_css(
+        &self,
+        property: &str,
+        value: Option<&Value>,
+        theme: &Theme,
+    ) -> Result<String, UtilError> {
+        let utility = match self.get(property, value.is_some()) {
+            Some(utility) => utility,
+            None => {
+                for plugin in &self.plugins {
+                    if let Some(declarations) = plugin.declarations(property, value, theme) {
+                        return declarations
+                            .map(|declarations| {
+                                declarations
+                                    .iter()
+                                    .map(|(property, value)| format!("{}:{};", property, value))
+                                    .collect()
+                            })
+                            .map_err(|message| UtilError::Plugin {
+                                property: property.to_string(),
+                                value: value.cloned(),
+                                message,
+                            });
+                    }
+                }
+                return Err(self.unknown_util(property, value));
+            }
+        };
+
+        utility.css(value, theme).map_err(|tried| {
+            let value = value.cloned().expect("Value fns are only tried on values");
+            let suggestions = match &value {
+                Value::Iden(iden) => suggest(
+                    iden,
+                    tried
+                        .iter()
+                        .filter_map(|value_fn| value_fn.known_values)
+                        .flat_map(|known_values| known_values(theme)),
+                ),
+                Value::Raw(_) => Vec::new(),
+            };
+            UtilError::InvalidValue {
+                property: property.to_string(),
+                value,
+                tried: tried.iter().map(|value_fn| value_fn.name).collect(),
+                suggestions,
+            }
+        })
+    }
+
+    /// No util or plugin util takes `property` like this
+    fn unknown_util(&self, property: &str, value: Option<&Value>) -> UtilError {
+        match value {
+            _ if !self.contains(property) => UtilError::UnknownUtil {
+                property: property.to_string(),
+                value: value.cloned(),
+                suggestions: suggest(property, self.names()),
+            },
+            Some(value) => UtilError::UnexpectedValue {
+                property: property.to_string(),
+                value: value.clone(),
+            },
+            None => UtilError::MissingValue {
+                property: property.to_string(),
+            },
+        }
+    }
+
+    /// The utils that come with ecss
+    pub fn builtin() -> Self {
+        Self {
+            plugins: Vec::new(),
+            utilities: vec![
+                // Shortcuts?
+                Utility::fixed("center", &[("display", "flex"), ("justify-content", "center"), ("align-items", "center")]),
+
+                // LAYOUT
+                // Display
+                Utility::fixed("block", &[("display", "block")]),
+                Utility::fixed("inline-block", &[("display", "inline-block")]),
+                Utility::fixed("inline", &[("display", "inline")]),
+                Utility::fixed("flex", &[("display", "flex")]),
+                Utility::fixed("inline-flex", &[("display", "inline-flex")]),
+                Utility::fixed("grid", &[("display", "grid")]),
+                Utility::fixed("inline-grid", &[("display", "inline-grid")]),
+                Utility::fixed("hidden", &[("display", "none")]),
+                //Overflow
+                Utility::valued("overflow", &["overflow"], value_fns![get_overflow_value]),
+                Utility::valued("overflow-x", &["overflow-x"], value_fns![get_overflow_value]),
+                Utility::valued("overflow-y", &["overflow-y"], value_fns![get_overflow_value]),
+                // Position
+                Utility::fixed("static", &[("position", "static")]),
+                Utility::fixed("fixed", &[("position", "fixed")]),
+                Utility::fixed("absolute", &[("position", "absolute")]),
+                Utility::fixed("relative", &[("position", "relative")]),
+                Utility::fixed("sticky", &[("position", "sticky")]),
+                // Top / Right / Bottom / Left
+                Utility::valued("t", &["top"], value_fns![get_auto_value, get_spacing_value, get_percentage_value]),
+                Utility::valued("r", &["right"], value_fns![get_auto_value, get_spacing_value, get_percentage_value]),
+                Utility::valued("b", &["bottom"], value_fns![get_auto_value, get_spacing_value, get_percentage_value]),
+                Utility::valued("l", &["left"], value_fns![get_auto_value, get_spacing_value, get_percentage_value]),
+                // Visibility
+                Utility::fixed("visible", &[("visibility", "visible")]),
+                Utility::fixed("invisible", &[("visibility", "hidden")]),
+                // Z-Index
+                Utility::valued("z", &["z-index"], value_fns![get_auto_value, get_unitless_number_value]),
+
+                // FLEXBOX AND GRID
+                Utility::valued("flex", &["flex"], value_fns![get_flex_value]),
+                Utility::valued("flex-b", &["flex-basis"], value_fns![get_spacing_value, get_percentage_value]),
+                Utility::valued("flex-d", &["flex-direction"], value_fns![get_flex_dir_value]),
+                Utility::valued("flex-w", &["flex-wrap"], value_fns![get_flex_wrap_value]),
+                Utility::valued("flex-g", &["flex-grow"], value_fns![get_flex_shrink_grow_value]),
+                Utility::valued("flex-s", &["flex-shrink"], value_fns![get_flex_shrink_grow_value]),
+                Utility::valued("grid-c", &["grid-template-columns"], value_fns![get_grid_cols_rows_value]),
+                Utility::valued("grid-r", &["grid-template-rows"], value_fns![get_grid_cols_rows_value]),
+                Utility::valued("gap", &["gap"], value_fns![get_spacing_value]),
+                Utility::valued("gap-x", &["column-gap"], value_fns![get_spacing_value]),
+                Utility::valued("gap-y", &["row-gap"], value_fns![get_spacing_value]),
+                Utility::valued("justify-c", &["justify-content"], value_fns![get_justify_align_content_value]),
+                Utility::valued("justify-i", &["justify-items"], value_fns![get_justify_items_value]),
+                Utility::valued("justify-s", &["justify-self"], value_fns![get_justify_items_value, get_auto_value]),
+                Utility::valued("align-c", &["align-content"], value_fns![get_justify_align_content_value]),
+                Utility::valued("align-i", &["align-items"], value_fns![get_align_items_value]),
+                Utility::valued("align-s", &["align-self"], value_fns![get_align_items_value, get_auto_value]),
+
+                // SPACING
+                Utility::valued("p", &["padding"], value_fns![get_spacing_value]),
+                Utility::valued("p-t", &["padding-top"], value_fns![get_spacing_value]),
+                Utility::valued("p-r", &["padding-right"], value_fns![get_spacing_value]),
+                Utility::valued("p-b", &["padding-bottom"], value_fns![get_spacing_value]),
+                Utility::valued("p-l", &["padding-left"], value_fns![get_spacing_value]),
+                Utility::valued("p-x", &["padding-left", "padding-right"], value_fns![get_spacing_value]),
+                Utility::valued("p-y", &["padding-top", "padding-bottom"], value_fns![get_spacing_value]),
+                Utility::valued("m", &["margin"], value_fns![get_spacing_value, get_auto_value]),
+                Utility::valued("m-t", &["margin-top"], value_fns![get_spacing_value, get_auto_value]),
+                Utility::valued("m-r", &["margin-right"], value_fns![get_spacing_value, get_auto_value]),
+                Utility::valued("m-b", &["margin-bottom"], value_fns![get_spacing_value, get_auto_value]),
+                Utility::valued("m-l", &["margin-left"], value_fns![get_spacing_value, get_auto_value]),
+                Utility::valued("m-x", &["margin-left", "margin-right"], value_fns![get_spacing_value, get_auto_value]),
+                Utility::valued("m-y", &["margin-top", "margin-bottom"], value_fns![get_spacing_value, get_auto_value]),
+
+                // SIZING
+                Utility::valued("w", &["width"], value_fns![get_spacing_value, get_percentage_value, get_width_value]),
+                Utility::valued("h", &["height"], value_fns![get_spacing_value, get_percentage_value, get_height_value]),
+                Utility::valued("max-w", &["max-width"], value_fns![get_max_width_value]),
+
+                // TYPOGRAPHY
+                Utility::valued("font-f", &["font-family"], value_fns![get_font_family_value]),
+                Utility::valued("font-s", &["font-size"], value_fns![get_font_size_value]).with(
+                    "line-height",
+                    DeclarationValue::OptionalValue(value_fns![get_font_size_line_height_value]),
+                ),
+                Utility::valued("font-w", &["font-weight"], value_fns![get_font_weight_value]),
+                Utility::valued("line-h", &["line-height"], value_fns![get_line_height_value, get_spacing_value]),
+                Utility::fixed("list-none", &[("list-style-type", "none")]),
+                Utility::fixed("list-disc", &[("list-style-type", "disc")]),
+                Utility::fixed("list-decimal", &[("list-style-type", "decimal")]),
+                Utility::fixed("list-inside", &[("list-style-position", "inside")]),
+                Utility::fixed("list-outside", &[("list-style-position", "outside")]),
+                Utility::fixed("font-italic", &[("font-style", "italic")]),
+                Utility::fixed("font-not-italic", &[("font-style", "normal")]),
+                Utility::fixed("antialiased", &[("-webkit-font-smoothing", "antialiased"), ("-moz-osx-font-smoothing", "grayscale")]),
+                Utility::fixed("subpixel-antialiased", &[("-webkit-font-smoothing", "auto"), ("-moz-osx-font-smoothing", "auto")]),
+                Utility::valued("text-a", &["text-align"], value_fns![get_text_align_value]),
+                Utility::valued("text-c", &["color"], value_fns![get_color_value]),
+                Utility::fixed("text-underline", &[("text-decoration", "underline")]),
+                Utility::fixed("text-line-through", &[("text-decoration", "line-through")]),
+                Utility::fixed("text-no-underline", &[("text-decoration", "none")]),
+                Utility::fixed("text-uppercase", &[("text-transform", "uppercase")]),
+                Utility::fixed("text-lowercase", &[("text-transform", "lowercase")]),
+                Utility::fixed("text-capitalize", &[("text-transform", "capitalize")]),
+                Utility::fixed("text-normal-case", &[("text-transform", "none")]),
+
+                // BACKGROUNDS
+                Utility::valued("bg-c", &["background-color"], value_fns![get_color_value]),
+
+                // BORDERS
+                Utility::valued("border-r", &["border-radius"], value_fns![get_border_radius_value]),
+                Utility::valued("border-w", &["border-width"], value_fns![get_px_value]),
+                Utility::valued("border-lw", &["border-left-width"], value_fns![get_px_value]),
+                Utility::valued("border-rw", &["border-right-width"], value_fns![get_px_value]),
+                Utility::valued("border-tw", &["border-top-width"], value_fns![get_px_value]),
+                Utility::valued("border-bw", &["border-bottom-width"], value_fns![get_px_value]),
+                Utility::valued("border-c", &["border-color"], value_fns![get_color_value]),
+                Utility::fixed("border-solid", &[("border-style", "solid")]),
+                Utility::fixed("border-dashed", &[("border-style", "dashed")]),
+                Utility::fixed("border-dotted", &[("border-style", "dotted")]),
+                Utility::fixed("border-double", &[("border-style", "double")]),
+                Utility::fixed("border-hidden", &[("border-style", "hidden")]),
+                Utility::fixed("border-none", &[("border-style", "none")]),
+
+                // Transitions & Animation
+                Utility::fixed("transition-none", &[("transition-property", "none")]),
+                Utility::fixed("transition-all", &[("transition-property", "all"), ("transition-timing-function", "cubic-bezier(0.4,0,0.2,1)"), ("transition-duration", "150ms")]),
+                Utility::fixed("transition", &[("transition-property", "background-color,border-color,color,fill,stroke,opacity,box-shadow,transform,filter,backdrop-filter"), ("transition-timing-function", "cubic-bezier(0.4,0,0.2,1)"), ("transition-duration", "150ms")]),
+                Utility::fixed("transition-colors", &[("transition-property", "background-color,border-color,color,fill,stroke"), ("transition-timing-function", "cubic-bezier(0.4,0,0.2,1)"), ("transition-duration", "150ms")]),
+                Utility::fixed("transition-opacity", &[("transition-property", "opacity"), ("transition-timing-function", "cubic-bezier(0.4,0,0.2,1)"), ("transition-duration", "150ms")]),
+                Utility::fixed("transition-shadow", &[("transition-property", "box-shadow"), ("transition-timing-function", "cubic-bezier(0.4,0,0.2,1)"), ("transition-duration", "150ms")]),
+                Utility::fixed("transition-transform", &[("transition-property", "transform"), ("transition-timing-function", "cubic-bezier(0.4,0,0.2,1)"), ("transition-duration", "150ms")]),
+                Utility::valued("transition-duration", &["transition-duration"], value_fns![get_ms_value]),
+                Utility::valued("transition-delay", &["transition-delay"], value_fns![get_ms_value]),
+                Utility::valued("transition-ease", &["transition-timing-function"], value_fns![get_transition_easing_value]),
+
+                //Interactivity
+                Utility::fixed("appearance-none", &[("appearance", "none")]),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggests_known_values_of_tried_value_fns() {
+        let theme = Theme::from_toml(
+            "[extend.colors]\nbrand = \"#ff5a1f\"\n[extend.spacing]\ngutter = \"1.5rem\"",
+        )
+        .unwrap();
+        let registry = UtilityRegistry::builtin();
+        let value = Value::Iden(String::from("bran"));
+        assert_eq!(
+            registry.util_css("bg-c", Some(&value), &theme),
+            Err(UtilError::InvalidValue {
+                property: String::from("bg-c"),
+                value: value.clone(),
+                tried: vec!["get_color_value"],
+                suggestions: vec![String::from("brand")],
+            })
+        );
+
+        // `get_auto_value` is tried too, but has no names to suggest
+        let value = Value::Iden(String::from("guter"));
+        assert_eq!(
+            registry
+                .util_css("m", Some(&value), &theme)
+                .unwrap_err()
+                .suggestions(),
+            ["gutter"]
+        );
+    }
+}
@@ -0,0 +1,60 @@
+/// At most this many suggestions are given
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Candidates close to `name`, closest first. A candidate is close when the
+/// edit distance is small for its length, or when it's a prefix of `name`
+/// (`bg-colour` -> `bg-c`).
+pub fn suggest<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let max_distance = name.chars().count() / 3;
+    let mut close: Vec<(usize, usize, &str)> = candidates
+        .filter(|candidate| *candidate != name)
+        .filter_map(|candidate| {
+            let distance = edit_distance(name, candidate);
+            let is_prefix = candidate.len() > 1 && name.starts_with(candidate);
+            if distance <= max_distance || is_prefix {
+                let len_diff = (name.len() as isize - candidate.len() as isize).unsigned_abs();
+                Some((distance, len_diff, candidate))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    // Stable, keeps the table order for ties
+    close.sort_by_key(|(distance, len_diff, _)| (*distance, *len_diff));
+    close.dedup_by_key(|(_, _, candidate)| *candidate);
+    close
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, _, candidate)| candidate.to_string())
+        .collect()
+}
+
+/// Levenshtein distance where swapping two adjacent chars also counts as one edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // distances[i][j] is the distance between a[..i] and b[..j]
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
@@ -1,22 +1,102 @@
 use anyhow::{bail, Result};
 
-use crate::ast::Value;
+use crate::{
+    ast::Value,
+    theme::{lookup, Theme},
+};
 
-pub fn get_spacing_value(value: &Value) -> Result<String> {
+/// Named spacing values of the default theme, numbers are a quarter rem per step
+pub const SPACING: &[(&str, &str)] = &[("0", "0px"), ("px", "1px")];
+
+pub const MAX_WIDTHS: &[(&str, &str)] = &[
+    ("0", "0rem"),
+    ("none", "none"),
+    ("xs", "20rem"),
+    ("sm", "24rem"),
+    ("md", "28rem"),
+    ("lg", "32rem"),
+    ("xl", "36rem"),
+    ("2xl", "42rem"),
+    ("3xl", "48rem"),
+    ("4xl", "56rem"),
+    ("5xl", "64rem"),
+    ("6xl", "72rem"),
+    ("7xl", "80rem"),
+    ("full", "100%"),
+    ("min", "min-content"),
+    ("max", "max-content"),
+    ("fit", "fit-content"),
+    ("prose", "65ch"),
+    ("screen-sm", "640px"),
+    ("screen-md", "768px"),
+    ("screen-lg", "1024px"),
+    ("screen-xl", "1280px"),
+    ("screen-2xl", "1536px"),
+];
+
+pub const FONT_FAMILIES: &[(&str, &str)] = &[
+    (
+        "sans",
+        r#"ui-sans-serif, system-ui, -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, "Helvetica Neue", Arial, "Noto Sans", sans-serif, "Apple Color Emoji", "Segoe UI Emoji", "Segoe UI Symbol", "Noto Color Emoji""#,
+    ),
+    (
+        "serif",
+        r#"ui-serif, Georgia, Cambria, "Times New Roman", Times, serif"#,
+    ),
+    (
+        "mono",
+        r#"ui-monospace, SFMono-Regular, Menlo, Monaco, Consolas, "Liberation Mono", "Courier New", monospace"#,
+    ),
+];
+
+/// Name, font size and line height
+pub const FONT_SIZES: &[(&str, &str, &str)] = &[
+    ("xs", "0.75rem", "1rem"),
+    ("sm", "0.875rem", "1.25rem"),
+    ("base", "1rem", "1.5rem"),
+    ("lg", "1.125rem", "1.75rem"),
+    ("xl", "1.25rem", "1.75rem"),
+    ("2xl", "1.5rem", "2rem"),
+    ("3xl", "1.875rem", "2.25rem"),
+    ("4xl", "2.25rem", "2.5rem"),
+    ("5xl", "3rem", "1"),
+    ("6xl", "3.75rem", "1"),
+    ("7xl", "4.5rem", "1"),
+    ("8xl", "6rem", "1"),
+    ("9xl", "8rem", "1"),
+];
+
+pub const BORDER_RADII: &[(&str, &str)] = &[
+    ("none", "0px"),
+    ("sm", "0.125rem"),
+    ("md", "0.25rem"),
+    ("lg", "0.375rem"),
+    ("xl", "0.5rem"),
+    ("2xl", "0.75rem"),
+    ("3xl", "1rem"),
+    ("4xl", "1.5rem"),
+    ("full", "9999px"),
+];
+
+pub fn get_spacing_value(value: &Value, theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
-        Value::Iden(iden) => Ok(match &**iden {
-            "0" => String::from("0px"),
-            "px" => String::from("1px"),
-            val => {
-                let nr = val.parse::<f32>()?;
-                format!("{}rem", nr / 4.0)
+        Value::Iden(iden) => {
+            if let Some(spacing) = lookup(&theme.spacing, iden) {
+                return Ok(spacing.clone());
             }
-        }),
+            match theme.spacing_step {
+                Some(step) => {
+                    let nr = iden.parse::<f32>()?;
+                    Ok(format!("{}rem", nr * step))
+                }
+                None => bail!("Unknown value: {}", iden),
+            }
+        }
     }
 }
 
-pub fn get_overflow_value(value: &Value) -> Result<String> {
+pub fn get_overflow_value(value: &Value, _theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
         Value::Iden(iden) => Ok(match &**iden {
@@ -30,7 +110,7 @@ pub fn get_overflow_value(value: &Value) -> Result<String> {
     }
 }
 
-pub fn get_justify_align_content_value(value: &Value) -> Result<String> {
+pub fn get_justify_align_content_value(value: &Value, _theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
         Value::Iden(iden) => Ok(match &**iden {
@@ -45,7 +125,7 @@ pub fn get_justify_align_content_value(value: &Value) -> Result<String> {
     }
 }
 
-pub fn get_justify_items_value(value: &Value) -> Result<String> {
+pub fn get_justify_items_value(value: &Value, _theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
         Value::Iden(iden) => Ok(match &**iden {
@@ -58,7 +138,7 @@ pub fn get_justify_items_value(value: &Value) -> Result<String> {
     }
 }
 
-pub fn get_align_items_value(value: &Value) -> Result<String> {
+pub fn get_align_items_value(value: &Value, _theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
         Value::Iden(iden) => Ok(match &**iden {
@@ -72,7 +152,7 @@ pub fn get_align_items_value(value: &Value) -> Result<String> {
     }
 }
 
-pub fn get_percentage_value(value: &Value) -> Result<String> {
+pub fn get_percentage_value(value: &Value, _theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
         Value::Iden(iden) => {
@@ -92,7 +172,7 @@ pub fn get_percentage_value(value: &Value) -> Result<String> {
     }
 }
 
-pub fn get_auto_value(value: &Value) -> Result<String> {
+pub fn get_auto_value(value: &Value, _theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
         Value::Iden(iden) => Ok(match &**iden {
@@ -102,7 +182,7 @@ pub fn get_auto_value(value: &Value) -> Result<String> {
     }
 }
 
-pub fn get_width_value(value: &Value) -> Result<String> {
+pub fn get_width_value(value: &Value, _theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
         Value::Iden(iden) => Ok(match &**iden {
@@ -112,7 +192,7 @@ pub fn get_width_value(value: &Value) -> Result<String> {
     }
 }
 
-pub fn get_height_value(value: &Value) -> Result<String> {
+pub fn get_height_value(value: &Value, _theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
         Value::Iden(iden) => Ok(match &**iden {
@@ -122,39 +202,17 @@ pub fn get_height_value(value: &Value) -> Result<String> {
     }
 }
 
-pub fn get_max_width_value(value: &Value) -> Result<String> {
+pub fn get_max_width_value(value: &Value, theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
-        Value::Iden(iden) => Ok(match &**iden {
-            "0" => String::from("0rem"),
-            "none" => String::from("none"),
-            "xs" => String::from("20rem"),
-            "sm" => String::from("24rem"),
-            "md" => String::from("28rem"),
-            "lg" => String::from("32rem"),
-            "xl" => String::from("36rem"),
-            "2xl" => String::from("42rem"),
-            "3xl" => String::from("48rem"),
-            "4xl" => String::from("56rem"),
-            "5xl" => String::from("64rem"),
-            "6xl" => String::from("72rem"),
-            "7xl" => String::from("80rem"),
-            "full" => String::from("100%"),
-            "min" => String::from("min-content"),
-            "max" => String::from("max-content"),
-            "fit" => String::from("fit-content"),
-            "prose" => String::from("65ch"),
-            "screen-sm" => String::from("640px"),
-            "screen-md" => String::from("768px"),
-            "screen-lg" => String::from("1024px"),
-            "screen-xl" => String::from("1280px"),
-            "screen-2xl" => String::from("1536px"),
-            _ => bail!("Unknown value: {}", iden),
-        }),
+        Value::Iden(iden) => match lookup(&theme.max_widths, iden) {
+            Some(value) => Ok(value.clone()),
+            None => bail!("Unknown value: {}", iden),
+        },
     }
 }
 
-pub fn get_flex_value(value: &Value) -> Result<String> {
+pub fn get_flex_value(value: &Value, _theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
         Value::Iden(iden) => Ok(match &**iden {
@@ -167,7 +225,7 @@ pub fn get_flex_value(value: &Value) -> Result<String> {
     }
 }
 
-pub fn get_flex_dir_value(value: &Value) -> Result<String> {
+pub fn get_flex_dir_value(value: &Value, _theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
         Value::Iden(iden) => Ok(match &**iden {
@@ -180,7 +238,7 @@ pub fn get_flex_dir_value(value: &Value) -> Result<String> {
     }
 }
 
-pub fn get_flex_wrap_value(value: &Value) -> Result<String> {
+pub fn get_flex_wrap_value(value: &Value, _theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
         Value::Iden(iden) => Ok(match &**iden {
@@ -192,7 +250,7 @@ pub fn get_flex_wrap_value(value: &Value) -> Result<String> {
     }
 }
 
-pub fn get_flex_shrink_grow_value(value: &Value) -> Result<String> {
+pub fn get_flex_shrink_grow_value(value: &Value, _theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
         Value::Iden(iden) => Ok(match &**iden {
@@ -203,7 +261,7 @@ pub fn get_flex_shrink_grow_value(value: &Value) -> Result<String> {
     }
 }
 
-pub fn get_grid_cols_rows_value(value: &Value) -> Result<String> {
+pub fn get_grid_cols_rows_value(value: &Value, _theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
         Value::Iden(iden) => {
@@ -213,69 +271,40 @@ pub fn get_grid_cols_rows_value(value: &Value) -> Result<String> {
     }
 }
 
-pub fn get_font_family_value(value: &Value) -> Result<String> {
+pub fn get_font_family_value(value: &Value, theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
-        Value::Iden(iden) => Ok(match &**iden {
-            "sans" => String::from(
-                r#"ui-sans-serif, system-ui, -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, "Helvetica Neue", Arial, "Noto Sans", sans-serif, "Apple Color Emoji", "Segoe UI Emoji", "Segoe UI Symbol", "Noto Color Emoji""#,
-            ),
-            "serif" => {
-                String::from(r#"ui-serif, Georgia, Cambria, "Times New Roman", Times, serif"#)
-            }
-            "mono" => String::from(
-                r#"ui-monospace, SFMono-Regular, Menlo, Monaco, Consolas, "Liberation Mono", "Courier New", monospace"#,
-            ),
-            _ => bail!("Unknown value: {}", iden),
-        }),
+        Value::Iden(iden) => match lookup(&theme.fonts, iden) {
+            Some(value) => Ok(value.clone()),
+            None => bail!("Unknown value: {}", iden),
+        },
     }
 }
 
-pub fn get_font_size_value(value: &Value) -> Result<String> {
+pub fn get_font_size_value(value: &Value, theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
-        Value::Iden(iden) => Ok(match &**iden {
-            "xs" => String::from("0.75rem"),
-            "sm" => String::from("0.875rem"),
-            "base" => String::from("1rem"),
-            "lg" => String::from("1.125rem"),
-            "xl" => String::from("1.25rem"),
-            "2xl" => String::from("1.5rem"),
-            "3xl" => String::from("1.875rem"),
-            "4xl" => String::from("2.25rem"),
-            "5xl" => String::from("3rem"),
-            "6xl" => String::from("3.75rem"),
-            "7xl" => String::from("4.5rem"),
-            "8xl" => String::from("6rem"),
-            "9xl" => String::from("8rem"),
-            _ => bail!("Unknown value: {}", iden),
-        }),
+        Value::Iden(iden) => match lookup(&theme.font_sizes, iden) {
+            Some(font_size) => Ok(font_size.size.clone()),
+            None => bail!("Unknown value: {}", iden),
+        },
     }
 }
 
-pub fn get_font_size_line_height_value(value: &Value) -> Result<String> {
+/// Line height that goes with a named font size
+pub fn get_font_size_line_height_value(value: &Value, theme: &Theme) -> Result<String> {
     match value {
-        Value::Raw(raw_value) => Ok(raw_value.clone()),
-        Value::Iden(iden) => Ok(match &**iden {
-            "xs" => String::from("1rem"),
-            "sm" => String::from("1.25rem"),
-            "base" => String::from("1.5rem"),
-            "lg" => String::from("1.75rem"),
-            "xl" => String::from("1.75rem"),
-            "2xl" => String::from("2rem"),
-            "3xl" => String::from("2.25rem"),
-            "4xl" => String::from("2.5rem"),
-            "5xl" => String::from("1"),
-            "6xl" => String::from("1"),
-            "7xl" => String::from("1"),
-            "8xl" => String::from("1"),
-            "9xl" => String::from("1"),
-            _ => bail!("Unknown value: {}", iden),
-        }),
+        Value::Raw(_) => bail!("Raw font sizes have no line height"),
+        Value::Iden(iden) => match lookup(&theme.font_sizes, iden)
+            .and_then(|font_size| font_size.line_height.as_ref())
+        {
+            Some(line_height) => Ok(line_height.clone()),
+            None => bail!("No line height for: {}", iden),
+        },
     }
 }
 
-pub fn get_font_weight_value(value: &Value) -> Result<String> {
+pub fn get_font_weight_value(value: &Value, _theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
         Value::Iden(iden) => Ok(match &**iden {
@@ -293,7 +322,7 @@ pub fn get_font_weight_value(value: &Value) -> Result<String> {
     }
 }
 
-pub fn get_text_align_value(value: &Value) -> Result<String> {
+pub fn get_text_align_value(value: &Value, _theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
         Value::Iden(iden) => Ok(match &**iden {
@@ -306,21 +335,13 @@ pub fn get_text_align_value(value: &Value) -> Result<String> {
     }
 }
 
-pub fn get_border_radius_value(value: &Value) -> Result<String> {
+pub fn get_border_radius_value(value: &Value, theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
-        Value::Iden(iden) => Ok(match &**iden {
-            "none" => String::from("0px"),
-            "sm" => String::from("0.125rem"),
-            "md" => String::from("0.25rem"),
-            "lg" => String::from("0.375rem"),
-            "xl" => String::from("0.5rem"),
-            "2xl" => String::from("0.75rem"),
-            "3xl" => String::from("1rem"),
-            "4xl" => String::from("1.5rem"),
-            "full" => String::from("9999px"),
-            _ => bail!("Unknown value: {}", iden),
-        }),
+        Value::Iden(iden) => match lookup(&theme.radii, iden) {
+            Some(value) => Ok(value.clone()),
+            None => bail!("Unknown value: {}", iden),
+        },
     }
 }
 
@@ -334,7 +355,19 @@ pub fn get_number_value(value: &Value, unit: &str) -> Result<String> {
     }
 }
 
-pub fn get_transition_easing_value(value: &Value) -> Result<String> {
+pub fn get_unitless_number_value(value: &Value, _theme: &Theme) -> Result<String> {
+    get_number_value(value, "")
+}
+
+pub fn get_px_value(value: &Value, _theme: &Theme) -> Result<String> {
+    get_number_value(value, "px")
+}
+
+pub fn get_ms_value(value: &Value, _theme: &Theme) -> Result<String> {
+    get_number_value(value, "ms")
+}
+
+pub fn get_transition_easing_value(value: &Value, _theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
         Value::Iden(iden) => Ok(match &**iden {
@@ -347,7 +380,7 @@ pub fn get_transition_easing_value(value: &Value) -> Result<String> {
     }
 }
 
-pub fn get_line_height_value(value: &Value) -> Result<String> {
+pub fn get_line_height_value(value: &Value, _theme: &Theme) -> Result<String> {
     match value {
         Value::Raw(raw_value) => Ok(raw_value.clone()),
         Value::Iden(iden) => Ok(match &**iden {
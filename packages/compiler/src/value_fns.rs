@@ -0,0 +1,13 @@
+pub use crate::utils::{
+    colors::get_color_value,
+    values::{
+        get_align_items_value, get_auto_value, get_border_radius_value, get_flex_dir_value,
+        get_flex_shrink_grow_value, get_flex_value, get_flex_wrap_value, get_font_family_value,
+        get_font_size_line_height_value, get_font_size_value, get_font_weight_value,
+        get_grid_cols_rows_value, get_height_value, get_justify_align_content_value,
+        get_justify_items_value, get_line_height_value, get_max_width_value, get_ms_value,
+        get_overflow_value, get_percentage_value, get_px_value, get_spacing_value,
+        get_text_align_value, get_transition_easing_value, get_unitless_number_value,
+        get_width_value,
+    },
+};